@@ -0,0 +1,160 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use filler::export::{
+    DEFAULT_CELL_SIZE, render_board, render_svg, render_turn, write_png, write_replay_gif,
+};
+use filler::replay::Replay;
+
+// Turns a recorded game into pictures without opening a window
+// The output's extension picks the format: .png or .svg for one board, .gif for
+// the whole game animated, and a directory for one numbered PNG per turn
+struct ExportOptions {
+    replay: String,
+    output: String,
+    cell_size: usize,
+    // 1-based, as the replay viewer counts; None for the final position
+    turn: Option<usize>,
+    delay: Duration,
+}
+
+fn main() {
+    let options = parse_args(env::args().skip(1)).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        eprintln!(
+            "usage: export <replay file> <output.png|.svg|.gif|directory/> \
+             [--cell PIXELS] [--turn N] [--delay MS]"
+        );
+        process::exit(2);
+    });
+
+    let replay = Replay::load(&options.replay).unwrap_or_else(|e| {
+        eprintln!("invalid replay {}", e);
+        process::exit(1);
+    });
+    if replay.turns.is_empty() {
+        eprintln!("{}: the replay has no turns", options.replay);
+        process::exit(1);
+    }
+    if let Some(turn) = options.turn
+        && turn > replay.turns.len()
+    {
+        eprintln!(
+            "--turn {} is past the last turn ({})",
+            turn,
+            replay.turns.len()
+        );
+        process::exit(2);
+    }
+
+    match export(&replay, &options) {
+        Ok(written) => eprintln!("{} written to {}", written, options.output),
+        Err(e) => {
+            eprintln!("failed to write {}: {}", options.output, e);
+            process::exit(1);
+        }
+    }
+}
+
+// Returns a description of what was written
+fn export(replay: &Replay, options: &ExportOptions) -> Result<String, String> {
+    let output = Path::new(&options.output);
+    let extension = output
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("gif") => {
+            let file = File::create(output).map_err(|e| e.to_string())?;
+            write_replay_gif(
+                replay,
+                options.cell_size,
+                options.delay,
+                BufWriter::new(file),
+            )
+            .map_err(|e| e.to_string())?;
+            Ok(format!("{} frames", replay.turns.len() + 1))
+        }
+        Some(format @ ("png" | "svg")) => {
+            let (board, highlight) = match options.turn {
+                Some(turn) => (
+                    replay.cells(turn - 1),
+                    replay.turns[turn - 1].placed_cells(),
+                ),
+                None => (replay.final_cells().unwrap(), Vec::new()),
+            };
+            if format == "svg" {
+                let svg = render_svg(&board, replay.player_number, options.cell_size, &highlight);
+                fs::write(output, svg).map_err(|e| e.to_string())?;
+            } else {
+                let image =
+                    render_board(&board, replay.player_number, options.cell_size, &highlight);
+                let file = File::create(output).map_err(|e| e.to_string())?;
+                write_png(&image, BufWriter::new(file)).map_err(|e| e.to_string())?;
+            }
+            Ok(match options.turn {
+                Some(turn) => format!("turn {}", turn),
+                None => "final position".to_string(),
+            })
+        }
+        Some(other) => Err(format!("unknown image format .{}", other)),
+        None => {
+            fs::create_dir_all(output).map_err(|e| e.to_string())?;
+            for turn in 0..replay.turns.len() {
+                let path = output.join(format!("turn-{:04}.png", turn + 1));
+                let file = File::create(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+                write_png(
+                    &render_turn(replay, turn, options.cell_size),
+                    BufWriter::new(file),
+                )
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            }
+            Ok(format!("{} turns", replay.turns.len()))
+        }
+    }
+}
+
+// Two positional arguments, then --cell, --turn and --delay
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<ExportOptions, String> {
+    let mut positional = Vec::new();
+    let mut cell_size = DEFAULT_CELL_SIZE;
+    let mut turn = None;
+    let mut delay = Duration::from_millis(250);
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg);
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        let number = value
+            .parse::<usize>()
+            .map_err(|_| format!("invalid value '{}' for {}", value, arg))?;
+
+        match arg.as_str() {
+            "--cell" if number > 0 => cell_size = number,
+            "--turn" if number > 0 => turn = Some(number),
+            "--delay" => delay = Duration::from_millis(number as u64),
+            "--cell" | "--turn" => return Err(format!("{} must be at least 1", arg)),
+            _ => return Err(format!("unknown option {}", arg)),
+        }
+    }
+
+    let [replay, output]: [String; 2] = positional
+        .try_into()
+        .map_err(|_| "expected a replay file and an output".to_string())?;
+    Ok(ExportOptions {
+        replay,
+        output,
+        cell_size,
+        turn,
+        delay,
+    })
+}
@@ -0,0 +1,191 @@
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{self, Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use filler::map::{Map, MapGeneratorConfig, generate_map};
+use filler::protocol::parse_move;
+use filler::referee::Referee;
+
+// Command line options, named after the official game engine flags
+struct Options {
+    map_path: String,
+    player_paths: [String; 2],
+    timeout: Duration,
+    seed: u64,
+    quiet: bool,
+}
+
+// A player executable with its stdin and a channel of reply lines
+struct PlayerProcess {
+    child: Child,
+    stdin: ChildStdin,
+    replies: Receiver<String>,
+}
+
+impl PlayerProcess {
+    // Spawns the player and sends the "$$$ exec pN" greeting
+    fn spawn(path: &str, number: u8) -> io::Result<Self> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let mut stdin = child.stdin.take().expect("player stdin is piped");
+        let stdout = child.stdout.take().expect("player stdout is piped");
+
+        // Read replies on a separate thread so a silent player can be timed out
+        let (sender, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        writeln!(stdin, "$$$ exec p{} : [{}]", number, path)?;
+        stdin.flush()?;
+
+        Ok(Self {
+            child,
+            stdin,
+            replies,
+        })
+    }
+
+    // Sends one turn and waits for the "x y" reply
+    // Returns None when the player crashed, timed out, or replied with garbage
+    fn request_move(&mut self, turn: &str, timeout: Duration) -> Option<(i32, i32)> {
+        self.stdin.write_all(turn.as_bytes()).ok()?;
+        self.stdin.flush().ok()?;
+        let reply = self.replies.recv_timeout(timeout).ok()?;
+        parse_move(&reply)
+    }
+}
+
+impl Drop for PlayerProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!(
+                "usage: referee -f <map> -p1 <player> -p2 <player> [-t <seconds>] [-s <seed>] [-q]"
+            );
+            process::exit(2);
+        }
+    };
+
+    let map = load_map(&options.map_path, options.seed).unwrap_or_else(|e| {
+        eprintln!("invalid map {}", e);
+        process::exit(1);
+    });
+    let mut referee = Referee::new(&map, options.seed);
+
+    let mut players = Vec::new();
+    for (index, path) in options.player_paths.iter().enumerate() {
+        match PlayerProcess::spawn(path, index as u8 + 1) {
+            Ok(player) => players.push(player),
+            Err(e) => {
+                eprintln!("failed to start player {} ({}): {}", index + 1, path, e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // Players alternate; a player that fails once is skipped for the rest of the game
+    while !referee.is_over() {
+        for (index, player) in players.iter_mut().enumerate() {
+            if !referee.active[index] {
+                continue;
+            }
+
+            let piece = referee.next_piece();
+            let turn = referee.format_turn(&piece);
+            let reply = player.request_move(&turn, options.timeout);
+            let accepted = referee.play_move(index, &piece, reply);
+
+            if !options.quiet {
+                let symbol = referee.players[index].territory_symbol;
+                match (reply, accepted) {
+                    (Some((x, y)), true) => println!("-> {} {} {}", symbol, x, y),
+                    (Some((x, y)), false) => println!("-> {} {} {} (invalid)", symbol, x, y),
+                    (None, _) => println!("-> {} no move", symbol),
+                }
+            }
+        }
+    }
+
+    let (score1, score2) = referee.scores();
+    println!("== @ fin: {}", score1);
+    println!("== $ fin: {}", score2);
+}
+
+// Resolves the -f argument: "random" for a generated map, a bundled map name, or a file path
+fn load_map(name: &str, seed: u64) -> Result<Map, String> {
+    if name == "random" {
+        return Ok(generate_map(&MapGeneratorConfig::default(), seed));
+    }
+    if !Path::new(name).exists()
+        && let Some(map) = Map::standard(name)
+    {
+        return Ok(map);
+    }
+    Map::load(name)
+}
+
+// Parses engine-style flags: -f, -p1, -p2, -t (seconds), -s (seed) and -q
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut map_path = None;
+    let mut player1 = None;
+    let mut player2 = None;
+    let mut timeout = Duration::from_secs(10);
+    let mut seed = 0;
+    let mut quiet = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "-file" => map_path = args.next(),
+            "-p1" | "-player1" => player1 = args.next(),
+            "-p2" | "-player2" => player2 = args.next(),
+            "-t" | "-time" => {
+                let value = args.next().ok_or("missing value for -t")?;
+                let seconds: u64 = value
+                    .parse()
+                    .map_err(|_| format!("invalid timeout '{}'", value))?;
+                timeout = Duration::from_secs(seconds);
+            }
+            "-s" | "-seed" => {
+                let value = args.next().ok_or("missing value for -s")?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("invalid seed '{}'", value))?;
+            }
+            "-q" | "-quiet" => quiet = true,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(Options {
+        map_path: map_path.ok_or("missing map (-f)")?,
+        player_paths: [
+            player1.ok_or("missing player 1 (-p1)")?,
+            player2.ok_or("missing player 2 (-p2)")?,
+        ],
+        timeout,
+        seed,
+        quiet,
+    })
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use filler::config::ScoringConfig;
+use filler::tuning::{TuningConfig, evolve};
+
+fn main() {
+    let (config, baseline, output) = match parse_args(env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!(
+                "usage: tune [--generations N] [--population N] [--games N] [--validation-games N] \
+                 [--seed N] [--baseline weights.toml] [--output best.toml]"
+            );
+            process::exit(2);
+        }
+    };
+
+    eprintln!(
+        "tuning: {} generations x {} candidates x {} games",
+        config.generations, config.population, config.games_per_candidate
+    );
+    let best = evolve(&config, &baseline, |generation, candidate| {
+        eprintln!(
+            "generation {:>3}: best share {:.3}  {}",
+            generation + 1,
+            candidate.fitness,
+            ScoringConfig::KEYS
+                .iter()
+                .map(|key| format!("{}={}", key, candidate.scoring.get(key).unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    });
+
+    // The report is a config file that `filler --config` accepts
+    let report = format!(
+        "# best share of claimed cells vs baseline on held-out maps: {:.3}\n{}",
+        best.fitness,
+        best.scoring.to_toml()
+    );
+    match output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, report) {
+                eprintln!("failed to write {}: {}", path, e);
+                process::exit(1);
+            }
+            eprintln!("best weights written to {}", path);
+        }
+        None => print!("{}", report),
+    }
+}
+
+// Parses --generations, --population, --games, --validation-games, --seed, --baseline
+// and --output
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(TuningConfig, ScoringConfig, Option<String>), String> {
+    let mut config = TuningConfig::default();
+    let mut baseline = ScoringConfig::default();
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| format!("invalid value '{}' for {}", value, arg))
+        };
+
+        match arg.as_str() {
+            "--generations" => config.generations = number()? as usize,
+            "--population" => config.population = number()? as usize,
+            "--games" => config.games_per_candidate = number()? as usize,
+            "--validation-games" => config.validation_games = number()? as usize,
+            "--seed" => config.seed = number()?,
+            "--baseline" => baseline = ScoringConfig::load(&value)?,
+            "--output" => output = Some(value),
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok((config, baseline, output))
+}
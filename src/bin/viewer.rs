@@ -0,0 +1,23 @@
+use std::env;
+use std::process;
+
+use filler::replay::Replay;
+use filler::visualizer::run_replay_viewer;
+
+// Steps through a game recorded with FILLER_REPLAY / --replay
+fn main() {
+    let mut args = env::args().skip(1);
+    let (Some(path), None) = (args.next(), args.next()) else {
+        eprintln!("usage: viewer <replay file>");
+        process::exit(2);
+    };
+
+    let replay = Replay::load(&path).unwrap_or_else(|e| {
+        eprintln!("invalid replay {}", e);
+        process::exit(1);
+    });
+    if let Err(e) = run_replay_viewer(&replay) {
+        eprintln!("viewer: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::board::{Board, Cell, Side};
+use crate::piece::Piece;
+
+// Widest board a bitboard can hold: one u128 per row
+pub const MAX_BITBOARD_WIDTH: usize = 128;
+
+// Packed board with one bit per cell, split into our cells, opponent cells and empty cells
+// Bit x of row y is column x. Legality checks become shift-and-AND operations per piece row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bitboard {
+    pub width: usize,
+    pub height: usize,
+    mine: Vec<u128>,
+    theirs: Vec<u128>,
+    empty: Vec<u128>,
+}
+
+// Piece pattern precompiled into one row mask per pattern row
+// Also records the bounding box of the solid cells so offsets outside the board are never tried
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompiledPiece {
+    pub rows: Vec<u128>,
+    // Solid cell bounds inside the pattern; None when the piece has no solid cell
+    pub solid_bounds: Option<SolidBounds>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolidBounds {
+    pub min_x: usize,
+    pub max_x: usize,
+    pub min_y: usize,
+    pub max_y: usize,
+}
+
+impl CompiledPiece {
+    // Compiles a piece; returns None when it is wider than a bitboard row
+    pub fn new(piece: &Piece) -> Option<Self> {
+        let mut rows = Vec::with_capacity(piece.pattern.len());
+        let mut solid_bounds: Option<SolidBounds> = None;
+
+        for (y, row) in piece.pattern.iter().enumerate() {
+            let mut mask = 0u128;
+            for (x, &cell) in row.iter().enumerate() {
+                if cell == '.' {
+                    continue;
+                }
+                if x >= MAX_BITBOARD_WIDTH {
+                    return None;
+                }
+                mask |= 1 << x;
+
+                let bounds = solid_bounds.get_or_insert(SolidBounds {
+                    min_x: x,
+                    max_x: x,
+                    min_y: y,
+                    max_y: y,
+                });
+                bounds.min_x = bounds.min_x.min(x);
+                bounds.max_x = bounds.max_x.max(x);
+                bounds.max_y = y;
+            }
+            rows.push(mask);
+        }
+
+        Some(Self { rows, solid_bounds })
+    }
+}
+
+impl Bitboard {
+    // Packs a board, keeping its point of view
+    // Returns None when the board is wider than MAX_BITBOARD_WIDTH
+    pub fn from_board(board: &Board) -> Option<Self> {
+        if board.width > MAX_BITBOARD_WIDTH {
+            return None;
+        }
+
+        let height = board.height;
+        let mut bitboard = Self {
+            width: board.width,
+            height,
+            mine: vec![0; height],
+            theirs: vec![0; height],
+            empty: vec![0; height],
+        };
+
+        for (x, y, cell) in board.iter() {
+            let bit = 1u128 << x;
+            if cell.is_mine() {
+                bitboard.mine[y] |= bit;
+            } else if cell.is_theirs() {
+                bitboard.theirs[y] |= bit;
+            } else {
+                bitboard.empty[y] |= bit;
+            }
+        }
+
+        Some(bitboard)
+    }
+
+    // Sets one cell, for patching the masks from a turn's board changes
+    pub fn set(&mut self, x: usize, y: usize, cell: Cell) {
+        let bit = 1u128 << x;
+        self.mine[y] &= !bit;
+        self.theirs[y] &= !bit;
+        self.empty[y] &= !bit;
+        if cell.is_mine() {
+            self.mine[y] |= bit;
+        } else if cell.is_theirs() {
+            self.theirs[y] |= bit;
+        } else {
+            self.empty[y] |= bit;
+        }
+    }
+
+    pub fn is_mine(&self, x: usize, y: usize) -> bool {
+        self.bit(&self.mine, x, y)
+    }
+
+    pub fn is_theirs(&self, x: usize, y: usize) -> bool {
+        self.bit(&self.theirs, x, y)
+    }
+
+    pub fn is_empty(&self, x: usize, y: usize) -> bool {
+        self.bit(&self.empty, x, y)
+    }
+
+    // Number of the side's own cells the piece would cover at (x, y)
+    // Returns None when a solid cell leaves the board or covers a cell of the other side
+    pub fn overlap_count(&self, piece: &CompiledPiece, x: i32, y: i32, side: Side) -> Option<u32> {
+        let bounds = piece.solid_bounds?;
+        if x + (bounds.min_x as i32) < 0
+            || x + (bounds.max_x as i32) >= self.width as i32
+            || y + (bounds.min_y as i32) < 0
+            || y + (bounds.max_y as i32) >= self.height as i32
+        {
+            return None;
+        }
+
+        let (own, blocked) = match side {
+            Side::Me => (&self.mine, &self.theirs),
+            Side::Opponent => (&self.theirs, &self.mine),
+        };
+        let mut overlaps = 0;
+        for piece_y in bounds.min_y..=bounds.max_y {
+            let mask = piece.rows[piece_y];
+            if mask == 0 {
+                continue;
+            }
+
+            // Bounds were checked above, so no solid bit is shifted out
+            let shifted = if x >= 0 { mask << x } else { mask >> -x };
+            let board_y = (y + piece_y as i32) as usize;
+            if shifted & blocked[board_y] != 0 {
+                return None;
+            }
+            overlaps += (shifted & own[board_y]).count_ones();
+        }
+
+        Some(overlaps)
+    }
+
+    // Lists every legal offset in row-major order, same as rules::valid_placements
+    pub fn valid_placements(&self, piece: &CompiledPiece, side: Side) -> Vec<(i32, i32)> {
+        let Some(bounds) = piece.solid_bounds else {
+            return Vec::new();
+        };
+        let mut valid_moves = Vec::new();
+
+        // Only offsets that keep every solid cell on the board
+        let min_y = -(bounds.min_y as i32);
+        let max_y = self.height as i32 - 1 - bounds.max_y as i32;
+        let min_x = -(bounds.min_x as i32);
+        let max_x = self.width as i32 - 1 - bounds.max_x as i32;
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                if self.overlap_count(piece, x, y, side) == Some(1) {
+                    valid_moves.push((x, y));
+                }
+            }
+        }
+
+        valid_moves
+    }
+
+    // Claims the piece's cells at (x, y) for the side, as rules::apply_placement does
+    // on the board; cells off the board are skipped
+    pub fn place(&mut self, piece: &CompiledPiece, x: i32, y: i32, side: Side) {
+        let width_mask = u128::MAX
+            .checked_shr((MAX_BITBOARD_WIDTH - self.width) as u32)
+            .unwrap_or(0);
+        for (piece_y, &mask) in piece.rows.iter().enumerate() {
+            let board_y = y + piece_y as i32;
+            if mask == 0 || board_y < 0 || board_y >= self.height as i32 {
+                continue;
+            }
+
+            let shifted = if x >= 0 {
+                mask.checked_shl(x as u32)
+            } else {
+                mask.checked_shr(x.unsigned_abs())
+            }
+            .unwrap_or(0)
+                & width_mask;
+            let row = board_y as usize;
+            let (own, other) = match side {
+                Side::Me => (&mut self.mine, &mut self.theirs),
+                Side::Opponent => (&mut self.theirs, &mut self.mine),
+            };
+            own[row] |= shifted;
+            other[row] &= !shifted;
+            self.empty[row] &= !shifted;
+        }
+    }
+
+    fn bit(&self, rows: &[u128], x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && rows[y] >> x & 1 == 1
+    }
+}
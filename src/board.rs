@@ -0,0 +1,213 @@
+use crate::distance::orthogonal_neighbours;
+use crate::player::Player;
+
+// One board cell from the AI's point of view
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Cell {
+    #[default]
+    Empty,
+    Mine,
+    // Our cells from the piece placed last turn
+    MineLast,
+    Theirs,
+    TheirsLast,
+}
+
+impl Cell {
+    // Maps an engine symbol to a cell; None for a symbol that is not a board cell
+    pub fn from_char(symbol: char, me: &Player, opponent: &Player) -> Option<Self> {
+        if symbol == '.' {
+            Some(Cell::Empty)
+        } else if symbol == me.territory_symbol {
+            Some(Cell::Mine)
+        } else if symbol == me.last_placed_symbol {
+            Some(Cell::MineLast)
+        } else if symbol == opponent.territory_symbol {
+            Some(Cell::Theirs)
+        } else if symbol == opponent.last_placed_symbol {
+            Some(Cell::TheirsLast)
+        } else {
+            None
+        }
+    }
+
+    // Engine symbol for the cell, the inverse of from_char
+    pub fn to_char(self, me: &Player, opponent: &Player) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Mine => me.territory_symbol,
+            Cell::MineLast => me.last_placed_symbol,
+            Cell::Theirs => opponent.territory_symbol,
+            Cell::TheirsLast => opponent.last_placed_symbol,
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self == Cell::Empty
+    }
+
+    pub fn is_mine(self) -> bool {
+        matches!(self, Cell::Mine | Cell::MineLast)
+    }
+
+    pub fn is_theirs(self) -> bool {
+        matches!(self, Cell::Theirs | Cell::TheirsLast)
+    }
+}
+
+// One of the two players, named from the board's point of view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Me,
+    Opponent,
+}
+
+impl Side {
+    pub fn other(self) -> Self {
+        match self {
+            Side::Me => Side::Opponent,
+            Side::Opponent => Side::Me,
+        }
+    }
+
+    // Whether the cell belongs to this side, last placed piece included
+    pub fn owns(self, cell: Cell) -> bool {
+        match self {
+            Side::Me => cell.is_mine(),
+            Side::Opponent => cell.is_theirs(),
+        }
+    }
+
+    pub fn territory(self) -> Cell {
+        match self {
+            Side::Me => Cell::Mine,
+            Side::Opponent => Cell::Theirs,
+        }
+    }
+
+    pub fn last_placed(self) -> Cell {
+        match self {
+            Side::Me => Cell::MineLast,
+            Side::Opponent => Cell::TheirsLast,
+        }
+    }
+}
+
+// Typed board parsed once from the engine grid
+// Cells are stored row-major; accessors take signed coordinates so placement
+// offsets can be checked without casting at every call site
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Board {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Board {
+    // Empty board of the given size
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::Empty; width * height],
+        }
+    }
+
+    // Parses an engine grid from the given player's point of view
+    // Fails on rows of different lengths and on symbols that are not board cells
+    pub fn from_chars(grid: &[Vec<char>], me: &Player, opponent: &Player) -> Result<Self, String> {
+        let width = grid.first().map_or(0, Vec::len);
+        let mut board = Self::new(width, grid.len());
+        for (y, row) in grid.iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "row {} has {} cells, expected {}",
+                    y,
+                    row.len(),
+                    width
+                ));
+            }
+            for (x, &symbol) in row.iter().enumerate() {
+                board.cells[y * width + x] = Cell::from_char(symbol, me, opponent)
+                    .ok_or_else(|| format!("unknown cell {:?} at ({}, {})", symbol, x, y))?;
+            }
+        }
+        Ok(board)
+    }
+
+    // Engine grid for the board, the inverse of from_chars
+    pub fn to_chars(&self, me: &Player, opponent: &Player) -> Vec<Vec<char>> {
+        self.cells
+            .chunks(self.width.max(1))
+            .take(self.height)
+            .map(|row| row.iter().map(|cell| cell.to_char(me, opponent)).collect())
+            .collect()
+    }
+
+    // Checks whether signed board coordinates fall inside the board
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32
+    }
+
+    // Cell at signed coordinates, None when off the board
+    pub fn get(&self, x: i32, y: i32) -> Option<Cell> {
+        if self.in_bounds(x, y) {
+            Some(self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    // Cell at coordinates known to be on the board
+    pub fn cell(&self, x: usize, y: usize) -> Cell {
+        assert!(
+            x < self.width && y < self.height,
+            "cell ({x}, {y}) is off the board"
+        );
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, cell: Cell) {
+        assert!(
+            x < self.width && y < self.height,
+            "cell ({x}, {y}) is off the board"
+        );
+        self.cells[y * self.width + x] = cell;
+    }
+
+    // Turns every `from` cell into `to`
+    pub fn replace(&mut self, from: Cell, to: Cell) {
+        for cell in self.cells.iter_mut().filter(|cell| **cell == from) {
+            *cell = to;
+        }
+    }
+
+    // Every cell with its coordinates in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, Cell)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, &cell)| (i % self.width, i / self.width, cell))
+    }
+
+    // The up-to-eight in-bounds neighbours of a cell, diagonals included
+    pub fn neighbours(&self, x: i32, y: i32) -> impl Iterator<Item = (i32, i32, Cell)> + '_ {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| dx != 0 || dy != 0)
+            .filter_map(move |(dx, dy)| {
+                let (nx, ny) = (x + dx, y + dy);
+                self.get(nx, ny).map(|cell| (nx, ny, cell))
+            })
+    }
+
+    // The up-to-four in-bounds orthogonal neighbours of a cell
+    pub fn orthogonal_neighbours(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (usize, usize, Cell)> + '_ {
+        orthogonal_neighbours(x, y, self.width, self.height)
+            .map(|(nx, ny)| (nx, ny, self.cell(nx, ny)))
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+
+// Weights and constants used by FillerAi's placement scoring
+// Defaults reproduce the original hand-tuned values; terms added since then carry
+// the weight that won the most cells against the original ones in self-play
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoringConfig {
+    pub heat_weight: i32,
+    pub blocking_weight: i32,
+    pub expansion_weight: i32,
+    pub efficiency_weight: i32,
+    // Weight on the Voronoi margin: empty cells we reach first minus cells they reach first
+    pub territory_weight: i32,
+    // Weight on placed cells where the opponent model expects the opponent to grow next
+    pub anticipation_weight: i32,
+    // Added per opponent cell next to a placed piece cell
+    pub proximity_bonus: i32,
+    // Multiplier on the number of solid cells placed
+    pub efficiency_multiplier: i32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            heat_weight: 100,
+            blocking_weight: 20,
+            expansion_weight: 5,
+            efficiency_weight: 10,
+            territory_weight: 150,
+            anticipation_weight: 100,
+            proximity_bonus: 5,
+            efficiency_multiplier: 2,
+        }
+    }
+}
+
+impl ScoringConfig {
+    // Keys accepted in config files; the environment variable is FILLER_ + the key in upper case
+    pub const KEYS: &'static [&'static str] = &[
+        "heat_weight",
+        "blocking_weight",
+        "expansion_weight",
+        "efficiency_weight",
+        "territory_weight",
+        "anticipation_weight",
+        "proximity_bonus",
+        "efficiency_multiplier",
+    ];
+
+    // Parses a config file; keys that are not present keep their default
+    // Accepts flat TOML ("key = value", optional [scoring] table) or a flat JSON object
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        let trimmed = text.trim();
+
+        if let Some(body) = trimmed.strip_prefix('{') {
+            let body = body.strip_suffix('}').ok_or("JSON object is not closed")?;
+            for entry in body.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let (key, value) = entry
+                    .split_once(':')
+                    .ok_or_else(|| format!("expected \"key\": value, found '{}'", entry))?;
+                config.set(key.trim().trim_matches('"'), value.trim())?;
+            }
+        } else {
+            for (line_idx, line) in text.lines().enumerate() {
+                let line = line.split('#').next().unwrap_or("").trim();
+                if line.is_empty() || line == "[scoring]" {
+                    continue;
+                }
+                let (key, value) = line
+                    .split_once('=')
+                    .ok_or_else(|| format!("line {}: expected key = value", line_idx + 1))?;
+                config
+                    .set(key.trim(), value.trim())
+                    .map_err(|e| format!("line {}: {}", line_idx + 1, e))?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    // Reads and parses a config file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // Overrides values from FILLER_HEAT_WEIGHT, FILLER_BLOCKING_WEIGHT, ...
+    pub fn apply_env(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        for key in Self::KEYS {
+            let variable = format!("FILLER_{}", key.to_uppercase());
+            if let Some(value) = env(&variable) {
+                self.set(key, value.trim())
+                    .map_err(|e| format!("{}: {}", variable, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    // Returns one value by key
+    pub fn get(&self, key: &str) -> Option<i32> {
+        match key {
+            "heat_weight" => Some(self.heat_weight),
+            "blocking_weight" => Some(self.blocking_weight),
+            "expansion_weight" => Some(self.expansion_weight),
+            "efficiency_weight" => Some(self.efficiency_weight),
+            "territory_weight" => Some(self.territory_weight),
+            "anticipation_weight" => Some(self.anticipation_weight),
+            "proximity_bonus" => Some(self.proximity_bonus),
+            "efficiency_multiplier" => Some(self.efficiency_multiplier),
+            _ => None,
+        }
+    }
+
+    // Formats the config as a TOML file that parse/load read back
+    pub fn to_toml(&self) -> String {
+        let mut text = String::from("[scoring]\n");
+        for key in Self::KEYS {
+            let value = self.get(key).unwrap_or_default();
+            text.push_str(&format!("{} = {}\n", key, value));
+        }
+        text
+    }
+
+    // Sets one value by key
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let value: i32 = value
+            .parse()
+            .map_err(|_| format!("'{}' is not an integer for {}", value, key))?;
+        let slot = match key {
+            "heat_weight" => &mut self.heat_weight,
+            "blocking_weight" => &mut self.blocking_weight,
+            "expansion_weight" => &mut self.expansion_weight,
+            "efficiency_weight" => &mut self.efficiency_weight,
+            "territory_weight" => &mut self.territory_weight,
+            "anticipation_weight" => &mut self.anticipation_weight,
+            "proximity_bonus" => &mut self.proximity_bonus,
+            "efficiency_multiplier" => &mut self.efficiency_multiplier,
+            _ => return Err(format!("unknown key '{}'", key)),
+        };
+        *slot = value;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::{Duration, Instant};
+
+// Point in time a move has to be ready by
+// Strategies check it between units of work and return the best move found so
+// far once it has passed; Deadline::never() is used for offline play and tuning
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Deadline {
+    expires: Option<Instant>,
+}
+
+impl Deadline {
+    // Expires `budget` from now
+    pub fn after(budget: Duration) -> Self {
+        Self {
+            expires: Some(Instant::now() + budget),
+        }
+    }
+
+    // Never expires
+    pub fn never() -> Self {
+        Self { expires: None }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.expires
+            .is_some_and(|expires| Instant::now() >= expires)
+    }
+
+    // Time left, None when the deadline never expires
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires
+            .map(|expires| expires.saturating_duration_since(Instant::now()))
+    }
+
+    // The earlier of two deadlines
+    pub fn min(self, other: Deadline) -> Deadline {
+        match (self.expires, other.expires) {
+            (Some(a), Some(b)) => Self {
+                expires: Some(a.min(b)),
+            },
+            (Some(_), None) => self,
+            _ => other,
+        }
+    }
+}
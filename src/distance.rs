@@ -0,0 +1,180 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// Multi-source BFS distance field over a grid
+// Distances grow by one per orthogonal step and only travel through open cells,
+// so occupied territory acts as a wall. Runs in O(width * height).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DistanceField {
+    pub width: usize,
+    pub height: usize,
+    // Row-major distances; None for cells the BFS never reached
+    distances: Vec<Option<u32>>,
+}
+
+impl DistanceField {
+    // Runs the BFS from every source cell, expanding only into open cells
+    // Sources get distance 0 whether or not they are open
+    pub fn compute(
+        width: usize,
+        height: usize,
+        is_source: impl Fn(usize, usize) -> bool,
+        is_open: impl Fn(usize, usize) -> bool,
+    ) -> Self {
+        let mut distances = vec![None; width * height];
+        let mut queue = VecDeque::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                if is_source(x, y) {
+                    distances[y * width + x] = Some(0);
+                    queue.push_back((x, y));
+                }
+            }
+        }
+
+        while let Some((x, y)) = queue.pop_front() {
+            let next = distances[y * width + x].unwrap_or(0) + 1;
+            for (nx, ny) in orthogonal_neighbours(x, y, width, height) {
+                let index = ny * width + nx;
+                if distances[index].is_none() && is_open(nx, ny) {
+                    distances[index] = Some(next);
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            distances,
+        }
+    }
+
+    // Turns cells into sources after the board changed
+    // Distances only shrink, so a BFS from the new sources fixes everything
+    // Returns the cells whose distance changed
+    pub fn add_sources(
+        &mut self,
+        sources: &[(usize, usize)],
+        is_open: impl Fn(usize, usize) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let mut changed = Vec::new();
+        let mut queue = VecDeque::new();
+
+        for &(x, y) in sources {
+            let index = y * self.width + x;
+            if self.distances[index] != Some(0) {
+                self.distances[index] = Some(0);
+                changed.push((x, y));
+            }
+            queue.push_back((x, y));
+        }
+
+        while let Some((x, y)) = queue.pop_front() {
+            let next = self.distances[y * self.width + x].unwrap_or(0) + 1;
+            for (nx, ny) in orthogonal_neighbours(x, y, self.width, self.height) {
+                let index = ny * self.width + nx;
+                if self.distances[index].is_none_or(|d| d > next) && is_open(nx, ny) {
+                    self.distances[index] = Some(next);
+                    changed.push((nx, ny));
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        changed
+    }
+
+    // Turns open cells into walls after the board changed
+    // Distances only grow: cells that lost every neighbour one step closer to a
+    // source are invalidated in distance order, then refilled from the valid cells
+    // around them. Work is proportional to the affected area, not the board.
+    // Returns the cells whose distance changed, walls included
+    pub fn close(
+        &mut self,
+        walls: &[(usize, usize)],
+        is_source: impl Fn(usize, usize) -> bool,
+        is_open: impl Fn(usize, usize) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let width = self.width;
+        let mut changed = Vec::new();
+        let mut invalidated = Vec::new();
+        let mut heap = BinaryHeap::new();
+
+        for &(x, y) in walls {
+            if let Some(distance) = self.distances[y * width + x].take() {
+                heap.push(Reverse((distance, x, y)));
+            }
+            changed.push((x, y));
+        }
+
+        // A cell at distance d + 1 stays valid while one neighbour is still at d
+        while let Some(Reverse((distance, x, y))) = heap.pop() {
+            for (nx, ny) in orthogonal_neighbours(x, y, width, self.height) {
+                if self.distances[ny * width + nx] != Some(distance + 1) || is_source(nx, ny) {
+                    continue;
+                }
+                let supported = orthogonal_neighbours(nx, ny, width, self.height)
+                    .any(|(sx, sy)| self.distances[sy * width + sx] == Some(distance));
+                if !supported {
+                    self.distances[ny * width + nx] = None;
+                    invalidated.push((nx, ny));
+                    heap.push(Reverse((distance + 1, nx, ny)));
+                }
+            }
+        }
+
+        // Refill the invalidated cells from their still-valid neighbours
+        for &(x, y) in &invalidated {
+            let best = orthogonal_neighbours(x, y, width, self.height)
+                .filter_map(|(nx, ny)| self.distances[ny * width + nx])
+                .min();
+            if let Some(best) = best {
+                heap.push(Reverse((best + 1, x, y)));
+            }
+        }
+        while let Some(Reverse((distance, x, y))) = heap.pop() {
+            let index = y * width + x;
+            if self.distances[index].is_some_and(|d| d <= distance) {
+                continue;
+            }
+            self.distances[index] = Some(distance);
+            for (nx, ny) in orthogonal_neighbours(x, y, width, self.height) {
+                if self.distances[ny * width + nx].is_none_or(|d| d > distance + 1)
+                    && is_open(nx, ny)
+                {
+                    heap.push(Reverse((distance + 1, nx, ny)));
+                }
+            }
+        }
+
+        changed.extend(invalidated);
+        changed
+    }
+
+    // Distance of a cell, None when out of bounds or unreachable
+    pub fn get(&self, x: usize, y: usize) -> Option<u32> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.distances[y * self.width + x]
+    }
+}
+
+// The up-to-four in-bounds orthogonal neighbours of a cell
+pub fn orthogonal_neighbours(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = (usize, usize)> {
+    [
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (x, y.wrapping_sub(1)),
+        (x, y + 1),
+    ]
+    .into_iter()
+    .filter(move |&(nx, ny)| nx < width && ny < height)
+}
@@ -0,0 +1,198 @@
+use crate::board::{Board, Cell, Side};
+use crate::deadline::Deadline;
+use crate::piece::Piece;
+use crate::regions::{Reach, RegionAnalysis};
+use crate::rules::anchored_placements;
+use crate::search::default_likely_pieces;
+
+// Empty cells split by which players can still claim them
+// `reach` is the furthest a piece claims from the owned cell it covers (see
+// Piece::reach); pieces have gaps, so one with reach 2 can claim across a one-cell wall
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Separation {
+    pub mine: usize,
+    pub theirs: usize,
+    pub shared: usize,
+    pub unreachable: usize,
+}
+
+impl Separation {
+    pub fn compute(board: &Board, reach: usize) -> Self {
+        Self::from_regions(board, &RegionAnalysis::compute(board), reach)
+    }
+
+    // Summed over the regions, which are exact for a reach of 1; wider pieces can
+    // only join regions, so the cells are rechecked one by one when none is shared
+    pub fn from_regions(board: &Board, regions: &RegionAnalysis, reach: usize) -> Self {
+        let by_region = Self {
+            mine: regions.cells_with_reach(Reach::Mine),
+            theirs: regions.cells_with_reach(Reach::Theirs),
+            shared: regions.cells_with_reach(Reach::Both),
+            unreachable: regions.cells_with_reach(Reach::Neither),
+        };
+        if by_region.shared > 0 || reach <= 1 {
+            return by_region;
+        }
+        Self::within_reach(board, reach)
+    }
+
+    // Empty cells each player could claim over the coming turns, every claimed
+    // cell letting the next piece reach further
+    pub fn within_reach(board: &Board, reach: usize) -> Self {
+        let mine = claimable(board, Side::Me, reach);
+        let theirs = claimable(board, Side::Opponent, reach);
+
+        let mut separation = Self::default();
+        for (x, y, cell) in board.iter() {
+            if !cell.is_empty() {
+                continue;
+            }
+            let index = y * board.width + x;
+            match (mine[index], theirs[index]) {
+                (true, true) => separation.shared += 1,
+                (true, false) => separation.mine += 1,
+                (false, true) => separation.theirs += 1,
+                (false, false) => separation.unreachable += 1,
+            }
+        }
+        separation
+    }
+
+    // Nothing is left to fight over: every empty cell we can still claim is ours alone
+    pub fn is_endgame(&self) -> bool {
+        self.shared == 0
+    }
+}
+
+// Empty cells within `reach` (in both directions) of the side's territory or of
+// another claimable cell, whatever lies between them
+fn claimable(board: &Board, side: Side, reach: usize) -> Vec<bool> {
+    let mut claimable = vec![false; board.width * board.height];
+    let mut stack: Vec<(usize, usize)> = board
+        .iter()
+        .filter(|&(_, _, cell)| side.owns(cell))
+        .map(|(x, y, _)| (x, y))
+        .collect();
+
+    while let Some((x, y)) = stack.pop() {
+        for ny in y.saturating_sub(reach)..=(y + reach).min(board.height - 1) {
+            for nx in x.saturating_sub(reach)..=(x + reach).min(board.width - 1) {
+                let index = ny * board.width + nx;
+                if board.cell(nx, ny).is_empty() && !claimable[index] {
+                    claimable[index] = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+    }
+    claimable
+}
+
+// Packs pieces into our private area once the players are separated
+// The opponent cannot interfere any more, so the goal is to keep as much of the
+// area fillable as possible: each move is judged by how many empty cells the likely
+// next pieces could still cover afterwards, then by how tightly it hugs walls
+pub struct Packer {
+    // Future pieces to keep room for; single cells are left out as they fill nothing
+    pub pieces: Vec<Piece>,
+}
+
+impl Default for Packer {
+    fn default() -> Self {
+        Self {
+            pieces: default_likely_pieces()
+                .into_iter()
+                .filter(|piece| piece.solid_cells().len() > 1)
+                .collect(),
+        }
+    }
+}
+
+impl Packer {
+    // Best packing move among `moves`, scoring them in order until the deadline
+    pub fn best_move(
+        &self,
+        board: &Board,
+        frontier: &[(usize, usize)],
+        piece: &Piece,
+        moves: &[(i32, i32)],
+        deadline: Deadline,
+    ) -> Option<(i32, i32)> {
+        let mut best = None;
+        let mut best_score = i32::MIN;
+
+        for &(x, y) in moves {
+            if deadline.expired() && best.is_some() {
+                break;
+            }
+            let score = self.packing_score(board, frontier, piece, x, y);
+            if score > best_score {
+                best_score = score;
+                best = Some((x, y));
+            }
+        }
+
+        best
+    }
+
+    // Cells the likely pieces could still cover after the move (x10), plus the
+    // placed cells' edges against walls, territory or the board border
+    pub fn packing_score(
+        &self,
+        board: &Board,
+        frontier: &[(usize, usize)],
+        piece: &Piece,
+        x: i32,
+        y: i32,
+    ) -> i32 {
+        let placed: Vec<(usize, usize)> = piece
+            .solid_cells()
+            .into_iter()
+            .map(|(px, py)| ((x + px as i32) as usize, (y + py as i32) as usize))
+            .filter(|&(cx, cy)| board.cell(cx, cy).is_empty())
+            .collect();
+
+        let mut next = board.clone();
+        for &(cx, cy) in &placed {
+            next.set(cx, cy, Cell::MineLast);
+        }
+
+        let contact: i32 = placed
+            .iter()
+            .map(|&(cx, cy)| {
+                let open = next
+                    .orthogonal_neighbours(cx, cy)
+                    .filter(|(_, _, cell)| cell.is_empty())
+                    .count();
+                4 - open as i32
+            })
+            .sum();
+
+        // Owned cells that still touch empty space after the move
+        let anchors: Vec<(usize, usize)> = frontier
+            .iter()
+            .chain(&placed)
+            .copied()
+            .filter(|&(cx, cy)| {
+                next.neighbours(cx as i32, cy as i32)
+                    .any(|(_, _, cell)| cell.is_empty())
+            })
+            .collect();
+
+        let mut covered = vec![false; next.width * next.height];
+        for future in &self.pieces {
+            let solid = future.solid_cells();
+            for (fx, fy) in anchored_placements(&next, future, anchors.iter().copied()) {
+                for &(px, py) in &solid {
+                    let (cx, cy) = ((fx + px as i32) as usize, (fy + py as i32) as usize);
+                    if next.cell(cx, cy).is_empty() {
+                        covered[cy * next.width + cx] = true;
+                    }
+                }
+            }
+        }
+        let coverable = covered.iter().filter(|&&cell| cell).count() as i32;
+
+        coverable * 10 + contact
+    }
+}
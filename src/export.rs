@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::board::Board;
+use crate::palette::{BACKGROUND, BOARD_COLORS, BORDER_COLOR, PLACED_OUTLINE, Rgb, cell_color};
+use crate::replay::Replay;
+
+// Pixel size of one board cell when none is given
+pub const DEFAULT_CELL_SIZE: usize = 10;
+// Smallest cell that still fits a border, a fill and the two-pixel outline
+const MIN_CELL_SIZE: usize = 4;
+
+// An RGB raster, row-major
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        for row in y..y + height {
+            self.pixels[row * self.width + x..row * self.width + x + width].fill(color);
+        }
+    }
+
+    // One-pixel frame along the rectangle's edge
+    fn frame_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        self.fill_rect(x, y, width, 1, color);
+        self.fill_rect(x, y + height - 1, width, 1, color);
+        self.fill_rect(x, y, 1, height, color);
+        self.fill_rect(x + width - 1, y, 1, height, color);
+    }
+}
+
+// Draws the board the way the visualizer does: each cell a `cell_size` square with
+// a one-pixel border, and the `highlight` cells outlined in gold
+pub fn render_board(
+    board: &Board,
+    my_player_number: u8,
+    cell_size: usize,
+    highlight: &[(usize, usize)],
+) -> Image {
+    let size = cell_size.max(MIN_CELL_SIZE);
+    let mut image = Image::new(board.width * size, board.height * size, BACKGROUND);
+
+    for (col, row, cell) in board.iter() {
+        let (x, y) = (col * size, row * size);
+        image.fill_rect(
+            x + 1,
+            y + 1,
+            size - 2,
+            size - 2,
+            cell_color(cell, my_player_number),
+        );
+        image.frame_rect(x, y, size, size, BORDER_COLOR);
+    }
+    for &(col, row) in highlight {
+        let (x, y) = (col * size, row * size);
+        image.frame_rect(x, y, size, size, PLACED_OUTLINE);
+        image.frame_rect(x + 1, y + 1, size - 2, size - 2, PLACED_OUTLINE);
+    }
+
+    image
+}
+
+// Same drawing as render_board, as an SVG document
+pub fn render_svg(
+    board: &Board,
+    my_player_number: u8,
+    cell_size: usize,
+    highlight: &[(usize, usize)],
+) -> String {
+    let size = cell_size.max(MIN_CELL_SIZE);
+    let (width, height) = (board.width * size, board.height * size);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n\
+         <rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>\n",
+        hex(BACKGROUND)
+    );
+
+    // Strokes are centred on the path, so a one-pixel border sits half a pixel in
+    for (col, row, cell) in board.iter() {
+        svg.push_str(&format!(
+            "<rect x=\"{}.5\" y=\"{}.5\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"{}\"/>\n",
+            col * size,
+            row * size,
+            size - 1,
+            size - 1,
+            hex(cell_color(cell, my_player_number)),
+            hex(BORDER_COLOR)
+        ));
+    }
+    for &(col, row) in highlight {
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"{}\" \
+             stroke-width=\"2\"/>\n",
+            col * size + 1,
+            row * size + 1,
+            size - 2,
+            size - 2,
+            hex(PLACED_OUTLINE)
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Writes the image as a PNG
+// Images with at most 256 colours (every board render) are stored with a palette
+pub fn write_png(image: &Image, out: impl Write) -> io::Result<()> {
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut index: HashMap<Rgb, u8> = HashMap::new();
+    for &pixel in &image.pixels {
+        if !index.contains_key(&pixel) && palette.len() < 256 {
+            index.insert(pixel, palette.len() as u8);
+            palette.push(pixel);
+        }
+    }
+    let indexed = image.pixels.iter().all(|pixel| index.contains_key(pixel));
+
+    let mut encoder = png::Encoder::new(out, image.width as u32, image.height as u32);
+    encoder.set_depth(png::BitDepth::Eight);
+    let data: Vec<u8> = if indexed {
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_palette(rgb_bytes(&palette));
+        image.pixels.iter().map(|pixel| index[pixel]).collect()
+    } else {
+        encoder.set_color(png::ColorType::Rgb);
+        rgb_bytes(&image.pixels)
+    };
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(())
+}
+
+// Writes an animated GIF one frame at a time, so a long game never sits in memory
+// Frames may only use the board colours, and each stores just the rectangle that
+// changed since the previous frame
+pub struct GifWriter<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: usize,
+    height: usize,
+    // Centiseconds each frame stays on screen
+    delay: u16,
+    previous: Option<Image>,
+}
+
+impl<W: Write> GifWriter<W> {
+    pub fn new(out: W, width: usize, height: usize, delay: Duration) -> io::Result<Self> {
+        if width == 0 || height == 0 || width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(invalid(format!(
+                "{}x{} is not a valid GIF size",
+                width, height
+            )));
+        }
+
+        let mut encoder =
+            gif::Encoder::new(out, width as u16, height as u16, &rgb_bytes(&BOARD_COLORS))
+                .map_err(gif_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(gif_error)?;
+
+        Ok(Self {
+            encoder,
+            width,
+            height,
+            delay: (delay.as_millis() / 10).min(u16::MAX as u128) as u16,
+            previous: None,
+        })
+    }
+
+    pub fn add_frame(&mut self, image: &Image) -> io::Result<()> {
+        if image.width != self.width || image.height != self.height {
+            return Err(invalid(format!(
+                "frame is {}x{}, the animation is {}x{}",
+                image.width, image.height, self.width, self.height
+            )));
+        }
+        let (left, top, width, height) = match &self.previous {
+            Some(previous) => changed_rect(previous, image).unwrap_or((0, 0, 1, 1)),
+            None => (0, 0, self.width, self.height),
+        };
+
+        let mut indices = Vec::with_capacity(width * height);
+        for y in top..top + height {
+            for x in left..left + width {
+                let pixel = image.pixel(x, y);
+                let index = BOARD_COLORS
+                    .iter()
+                    .position(|&color| color == pixel)
+                    .ok_or_else(|| invalid(format!("{:?} is not a board colour", pixel)))?;
+                indices.push(index as u8);
+            }
+        }
+
+        // Each frame is drawn over the previous one, which stays underneath
+        let mut frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, indices, None);
+        frame.left = left as u16;
+        frame.top = top as u16;
+        frame.delay = self.delay;
+        frame.dispose = gif::DisposalMethod::Keep;
+        self.encoder.write_frame(&frame).map_err(gif_error)?;
+
+        self.previous = Some(image.clone());
+        Ok(())
+    }
+
+    // Writes the trailer and hands back the output
+    pub fn finish(self) -> io::Result<W> {
+        let mut out = self.encoder.into_inner().map_err(gif_error)?;
+        out.flush()?;
+        Ok(out)
+    }
+}
+
+// A recorded turn with the cells of our answer outlined, as the replay viewer shows it
+pub fn render_turn(replay: &Replay, turn: usize, cell_size: usize) -> Image {
+    render_board(
+        &replay.cells(turn),
+        replay.player_number,
+        cell_size,
+        &replay.turns[turn].placed_cells(),
+    )
+}
+
+// Animates a whole recorded game: one frame per turn, then the final position
+pub fn write_replay_gif(
+    replay: &Replay,
+    cell_size: usize,
+    delay: Duration,
+    out: impl Write,
+) -> io::Result<()> {
+    let Some(last) = replay.final_cells() else {
+        return Err(invalid("the replay has no turns".to_string()));
+    };
+    let last = render_board(&last, replay.player_number, cell_size, &[]);
+
+    let mut gif = GifWriter::new(out, last.width, last.height, delay)?;
+    for turn in 0..replay.turns.len() {
+        gif.add_frame(&render_turn(replay, turn, cell_size))?;
+    }
+    gif.add_frame(&last)?;
+    gif.finish().map(drop)
+}
+
+// "#rrggbb"
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+// Smallest (left, top, width, height) holding every pixel that differs
+fn changed_rect(before: &Image, after: &Image) -> Option<(usize, usize, usize, usize)> {
+    let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+    for y in 0..after.height {
+        for x in 0..after.width {
+            if before.pixel(x, y) != after.pixel(x, y) {
+                (left, top) = (left.min(x), top.min(y));
+                (right, bottom) = (right.max(x), bottom.max(y));
+            }
+        }
+    }
+    (left != usize::MAX).then(|| (left, top, right - left + 1, bottom - top + 1))
+}
+
+// Colours as the flat r, g, b bytes both encoders take for a palette
+fn rgb_bytes(colors: &[Rgb]) -> Vec<u8> {
+    colors.iter().flat_map(|c| [c.r, c.g, c.b]).collect()
+}
+
+fn gif_error(error: gif::EncodingError) -> io::Error {
+    match error {
+        gif::EncodingError::Io(error) => error,
+        other => io::Error::other(other.to_string()),
+    }
+}
@@ -1,22 +1,47 @@
+use crate::bitboard::{Bitboard, CompiledPiece};
+use crate::board::{Board, Cell, Side};
+use crate::config::ScoringConfig;
+use crate::deadline::Deadline;
+use crate::endgame::{Packer, Separation};
+use crate::game_state::GameState;
+use crate::opponent::OpponentModel;
 use crate::piece::Piece;
 use crate::player::Player;
-#[cfg(feature = "visualizer")]
-use crate::visualizer::get_visualizer;
+use crate::rules::{
+    Position, anchored_placements_with, has_no_isolated_cells, valid_placements_with,
+};
+use crate::voronoi::territory_after_fill;
 
 // AI struct that manages game state for the Filler game
 // Contains board data, player information, and current piece details
+#[derive(Clone)]
 pub struct FillerAi {
     // Board data
     pub board_width: usize,
     pub board_height: usize,
-    pub board: Vec<Vec<char>>,
+    // The board from our side, parsed once per turn
+    pub cells: Board,
+    // The same board packed for move generation; None when it is too wide
+    pub bitboard: Option<Bitboard>,
     // Player info
     pub my_player: Player,
     pub opponent_player: Player,
     // Piece info
     pub current_piece: Piece,
+    // The current piece as bitboard row masks, compiled once per turn
+    pub compiled_piece: Option<CompiledPiece>,
+    // Widest reach among the pieces seen so far, at least 1, for the endgame check
+    pub piece_reach: usize,
     // Heat map for strategic placement
     pub heat_map: Vec<Vec<i32>>,
+    // Distance fields, frontiers and regions carried over between turns
+    pub state: GameState,
+    // Weights used by score_placement
+    pub scoring: ScoringConfig,
+    // Opponent placements seen so far, rebuilt from successive boards
+    pub opponent: OpponentModel,
+    // When the current move has to be ready; strategies return their best so far after it
+    pub deadline: Deadline,
 }
 
 impl FillerAi {
@@ -27,160 +52,194 @@ impl FillerAi {
         Self {
             board_width: 0,
             board_height: 0,
-            board: Vec::new(),
+            cells: Board::default(),
+            bitboard: None,
             my_player: Player::new(my_player_number),
             opponent_player: Player::new(opponent_number),
             current_piece: Piece::new(0, 0, Vec::new()),
+            compiled_piece: None,
+            piece_reach: 1,
             heat_map: Vec::new(),
+            state: GameState::default(),
+            scoring: ScoringConfig::default(),
+            opponent: OpponentModel::new(),
+            deadline: Deadline::never(),
         }
     }
 
     // Updates the AI's internal board state with new dimensions and layout
     // Replaces the existing board data with the provided width, height, and grid
-
-    pub fn update_board(&mut self, width: usize, height: usize, board: Vec<Vec<char>>) {
+    // Fails, keeping the previous board, when the grid does not match the size or
+    // holds a symbol that is not a board cell
+    pub fn update_board(
+        &mut self,
+        width: usize,
+        height: usize,
+        board: Vec<Vec<char>>,
+    ) -> Result<(), String> {
+        let cells = Board::from_chars(&board, &self.my_player, &self.opponent_player)?;
+        if (cells.width, cells.height) != (width, height) {
+            return Err(format!(
+                "board is {}x{}, expected {}x{}",
+                cells.width, cells.height, width, height
+            ));
+        }
         self.board_width = width;
         self.board_height = height;
-        self.board = board.clone();
 
-        // Initialize heat map
-        self.heat_map = vec![vec![0; width]; height];
-        self.generate_heat_map();
-
-        // Update visualizer with new board state
-        #[cfg(feature = "visualizer")]
-        get_visualizer().update_board(width, height, board);
+        // Only the cells that changed since last turn are fed through the state,
+        // the heat map and the bitboard
+        let their_cells: Vec<(usize, usize)> = match self.state.update(&self.cells, &cells) {
+            Some(changes) => {
+                self.cells = cells;
+                for (x, y) in changes.distance_changed {
+                    self.heat_map[y][x] = self.heat_at(x, y);
+                }
+                if let Some(bitboard) = &mut self.bitboard {
+                    for change in &changes.filled {
+                        bitboard.set(change.x, change.y, change.after);
+                    }
+                }
+                changes
+                    .filled
+                    .iter()
+                    .filter(|change| change.after.is_theirs())
+                    .map(|change| (change.x, change.y))
+                    .collect()
+            }
+            None => {
+                self.cells = cells;
+                self.heat_map = vec![vec![0; width]; height];
+                self.generate_heat_map();
+                self.bitboard = Bitboard::from_board(&self.cells);
+                // Nothing to diff against: their last piece is the one still marked
+                self.cells
+                    .iter()
+                    .filter(|&(_, _, cell)| cell == Cell::TheirsLast)
+                    .map(|(x, y, _)| (x, y))
+                    .collect()
+            }
+        };
+        self.opponent.observe(&self.cells, &their_cells);
+        Ok(())
     }
 
     // Updates the current piece that the AI needs to place
     // Replaces the existing piece with the new piece data
     pub fn update_piece(&mut self, piece: Piece) {
+        self.compiled_piece = CompiledPiece::new(&piece);
+        self.piece_reach = self.piece_reach.max(piece.reach());
         self.current_piece = piece;
     }
 
     // Generate heat map based on distance to opponent territory
+    // Distances come from a BFS that starts at every opponent cell and only walks
+    // through empty cells, so walls of territory lengthen the path and sealed-off
+    // cells get no heat at all
     pub fn generate_heat_map(&mut self) {
-        // Reset heat map
-        for row in &mut self.heat_map {
-            for cell in row.iter_mut() {
-                *cell = 0;
-            }
-        }
-
-        // Find all opponent positions
-        let mut opponent_positions = Vec::new();
         for y in 0..self.board_height {
             for x in 0..self.board_width {
-                let cell = self.board[y][x];
-                if cell == self.opponent_player.territory_symbol
-                    || cell == self.opponent_player.last_placed_symbol
-                {
-                    opponent_positions.push((x, y));
-                }
+                self.heat_map[y][x] = self.heat_at(x, y);
             }
         }
+    }
 
-        if opponent_positions.is_empty() {
-            return;
+    // Heat of one cell from the opponent distance field kept in the game state
+    fn heat_at(&self, x: usize, y: usize) -> i32 {
+        // Skip cells already occupied
+        if !self.cells.cell(x, y).is_empty() {
+            return 0;
         }
 
-        // Calculate heat for each cell based on Manhattan distance to nearest opponent
-        for y in 0..self.board_height {
-            for x in 0..self.board_width {
-                // Skip cells already occupied
-                let cell = self.board[y][x];
-                if cell != '.' {
-                    continue;
-                }
-
-                // Find minimum Manhattan distance to any opponent cell
-                let mut min_distance = i32::MAX;
-                for &(opp_x, opp_y) in &opponent_positions {
-                    let manhattan_dist =
-                        (x as i32 - opp_x as i32).abs() + (y as i32 - opp_y as i32).abs();
-                    min_distance = min_distance.min(manhattan_dist);
-                }
-
-                // Higher heat = closer to opponent (inverted distance)
-                // Use max possible distance minus actual distance for heat value
+        // Higher heat = closer to opponent (inverted distance)
+        // Use max possible distance minus actual distance for heat value;
+        // long detours still keep at least 1 so they beat unreachable cells
+        match self.state.opponent_distance.get(x, y) {
+            Some(distance) => {
                 let max_possible_dist = (self.board_width + self.board_height) as i32;
-                self.heat_map[y][x] = max_possible_dist - min_distance;
+                (max_possible_dist - distance as i32).max(1)
             }
+            None => 0,
         }
     }
 
     // Find all valid placements for current piece
-    pub fn find_all_valid_placements(&self) -> Vec<(usize, usize)> {
-        let mut valid_moves = Vec::new();
-
-        for y in 0..self.board_height {
-            'outer: for x in 0..self.board_width {
-                let mut cell_overlap_count = 0;
-
-                // Check each solid cell in the piece
-                for (piece_y, piece_row) in self.current_piece.pattern.iter().enumerate() {
-                    for (piece_x, piece_char) in piece_row.iter().enumerate() {
-                        if *piece_char == '.' {
-                            continue; // Skip empty piece cells
-                        }
-
-                        // Calculate where this piece cell would land on board
-                        let board_x = x + piece_x;
-                        let board_y = y + piece_y;
-
-                        // Check bounds
-                        if board_x >= self.board_width || board_y >= self.board_height {
-                            continue 'outer;
-                        }
-
-                        let board_cell = self.board[board_y][board_x];
-
-                        // Check opponent collision
-                        if board_cell == self.opponent_player.last_placed_symbol
-                            || board_cell == self.opponent_player.territory_symbol
-                        {
-                            continue 'outer;
-                        }
-
-                        // Count overlaps with my territory
-                        if board_cell == self.my_player.last_placed_symbol
-                            || board_cell == self.my_player.territory_symbol
-                        {
-                            cell_overlap_count += 1;
-                            if cell_overlap_count > 1 {
-                                continue 'outer;
-                            }
-                        }
-                    }
-                }
+    // Offsets may be negative when the piece's solid cells start after leading empty rows/columns
+    // Candidates are generated from our frontier: the owned cell a legal placement
+    // overlaps is one that touches empty space, unless the piece has a lone cell
+    pub fn find_all_valid_placements(&self) -> Vec<(i32, i32)> {
+        let piece = &self.current_piece;
+
+        if has_no_isolated_cells(piece) {
+            anchored_placements_with(
+                &self.cells,
+                self.bitboard.as_ref(),
+                piece,
+                self.compiled_piece.as_ref(),
+                self.state.my_frontier(),
+            )
+        } else if piece.solid_cells().len() == 1 {
+            // A single cell can sit on any owned cell
+            let owned = self
+                .cells
+                .iter()
+                .filter(|(_, _, cell)| cell.is_mine())
+                .map(|(x, y, _)| (x, y));
+            anchored_placements_with(
+                &self.cells,
+                self.bitboard.as_ref(),
+                piece,
+                self.compiled_piece.as_ref(),
+                owned,
+            )
+        } else {
+            valid_placements_with(
+                &self.cells,
+                self.bitboard.as_ref(),
+                piece,
+                self.compiled_piece.as_ref(),
+                Side::Me,
+            )
+        }
+    }
 
-                // Valid placement if exactly one overlap
-                if cell_overlap_count == 1 {
-                    valid_moves.push((x, y));
-                }
-            }
+    // The current board with its bitboard, for searches that play moves ahead
+    pub fn position(&self) -> Position {
+        Position {
+            board: self.cells.clone(),
+            bitboard: self.bitboard.clone(),
         }
+    }
 
-        valid_moves
+    // Checks whether signed board coordinates fall inside the board
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        self.cells.in_bounds(x, y)
+    }
+
+    // Board coordinates of the current piece's solid cells at a placement,
+    // skipping cells that fall off the board
+    pub fn placed_cells(
+        &self,
+        placement_x: i32,
+        placement_y: i32,
+    ) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.current_piece
+            .solid_cells()
+            .into_iter()
+            .map(move |(piece_x, piece_y)| {
+                (placement_x + piece_x as i32, placement_y + piece_y as i32)
+            })
+            .filter(|&(x, y)| self.in_bounds(x, y))
     }
 
     // Calculate heat score for a placement based on heat map
-    pub fn calculate_heat_score(&self, placement_x: usize, placement_y: usize) -> i32 {
+    pub fn calculate_heat_score(&self, placement_x: i32, placement_y: i32) -> i32 {
         let mut total_heat = 0;
         let mut solid_cells = 0;
 
-        for (piece_y, piece_row) in self.current_piece.pattern.iter().enumerate() {
-            for (piece_x, piece_char) in piece_row.iter().enumerate() {
-                if *piece_char != '.' {
-                    let board_x = placement_x + piece_x;
-                    let board_y = placement_y + piece_y;
-
-                    if board_x < self.board_width && board_y < self.board_height {
-                        total_heat += self.heat_map[board_y][board_x];
-                        solid_cells += 1;
-                    }
-                }
-            }
+        for (board_x, board_y) in self.placed_cells(placement_x, placement_y) {
+            total_heat += self.heat_map[board_y as usize][board_x as usize];
+            solid_cells += 1;
         }
 
         if solid_cells == 0 {
@@ -191,44 +250,14 @@ impl FillerAi {
     }
 
     // Simple blocking strategy: prefer positions near opponent
-    pub fn calculate_blocking_score(&self, placement_x: usize, placement_y: usize) -> i32 {
+    pub fn calculate_blocking_score(&self, placement_x: i32, placement_y: i32) -> i32 {
         let mut blocking_score = 0;
 
-        for (piece_y, piece_row) in self.current_piece.pattern.iter().enumerate() {
-            for (piece_x, piece_char) in piece_row.iter().enumerate() {
-                if *piece_char != '.' {
-                    let board_x = placement_x + piece_x;
-                    let board_y = placement_y + piece_y;
-
-                    if board_x < self.board_width && board_y < self.board_height {
-                        // Check immediate surrounding for opponent pieces
-                        for dy in -1..=1i32 {
-                            for dx in -1..=1i32 {
-                                if dx == 0 && dy == 0 {
-                                    continue;
-                                }
-
-                                let check_x = board_x as i32 + dx;
-                                let check_y = board_y as i32 + dy;
-
-                                if check_x >= 0
-                                    && check_x < self.board_width as i32
-                                    && check_y >= 0
-                                    && check_y < self.board_height as i32
-                                {
-                                    let check_x = check_x as usize;
-                                    let check_y = check_y as usize;
-                                    let cell = self.board[check_y][check_x];
-
-                                    if cell == self.opponent_player.last_placed_symbol
-                                        || cell == self.opponent_player.territory_symbol
-                                    {
-                                        blocking_score += 5; // Simple proximity bonus
-                                    }
-                                }
-                            }
-                        }
-                    }
+        for (board_x, board_y) in self.placed_cells(placement_x, placement_y) {
+            // Check immediate surrounding for opponent pieces
+            for (_, _, cell) in self.cells.neighbours(board_x, board_y) {
+                if cell.is_theirs() {
+                    blocking_score += self.scoring.proximity_bonus;
                 }
             }
         }
@@ -237,91 +266,133 @@ impl FillerAi {
     }
 
     // Calculate how many empty cells this placement opens up
-    pub fn calculate_expansion_score(&self, placement_x: usize, placement_y: usize) -> i32 {
-        let mut expansion_score = 0;
-
-        for (piece_y, piece_row) in self.current_piece.pattern.iter().enumerate() {
-            for (piece_x, piece_char) in piece_row.iter().enumerate() {
-                if *piece_char != '.' {
-                    let board_x = placement_x + piece_x;
-                    let board_y = placement_y + piece_y;
-
-                    if board_x < self.board_width && board_y < self.board_height {
-                        // Check adjacent cells for expansion potential
-                        for dy in -1..=1i32 {
-                            for dx in -1..=1i32 {
-                                if dx == 0 && dy == 0 {
-                                    continue;
-                                }
-
-                                let adj_x = board_x as i32 + dx;
-                                let adj_y = board_y as i32 + dy;
-
-                                if adj_x >= 0
-                                    && adj_x < self.board_width as i32
-                                    && adj_y >= 0
-                                    && adj_y < self.board_height as i32
-                                {
-                                    let adj_x = adj_x as usize;
-                                    let adj_y = adj_y as usize;
-
-                                    if self.board[adj_y][adj_x] == '.' {
-                                        expansion_score += 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        expansion_score
+    pub fn calculate_expansion_score(&self, placement_x: i32, placement_y: i32) -> i32 {
+        self.placed_cells(placement_x, placement_y)
+            .map(|(x, y)| self.state.regions.open_neighbours(x as usize, y as usize) as i32)
+            .sum()
     }
 
     // NEW: Evaluate piece size efficiency - prioritize larger impact pieces
-    pub fn calculate_piece_efficiency(&self, placement_x: usize, placement_y: usize) -> i32 {
-        let mut solid_count = 0;
+    pub fn calculate_piece_efficiency(&self, placement_x: i32, placement_y: i32) -> i32 {
+        let solid_count = self.placed_cells(placement_x, placement_y).count() as i32;
 
-        for (piece_y, piece_row) in self.current_piece.pattern.iter().enumerate() {
-            for (piece_x, piece_char) in piece_row.iter().enumerate() {
-                if *piece_char != '.' {
-                    let board_x = placement_x + piece_x;
-                    let board_y = placement_y + piece_y;
+        solid_count * self.scoring.efficiency_multiplier // Bonus for placing larger pieces
+    }
 
-                    if board_x < self.board_width && board_y < self.board_height {
-                        solid_count += 1;
-                    }
-                }
+    // Voronoi margin after the placement: empty cells we would reach first minus
+    // cells the opponent would reach first
+    // Patched from the distance fields kept in the game state, so only the cells
+    // whose distance the piece changes are recomputed
+    pub fn calculate_territory_score(&self, placement_x: i32, placement_y: i32) -> i32 {
+        let placed: Vec<(usize, usize)> = self
+            .placed_cells(placement_x, placement_y)
+            .map(|(x, y)| (x as usize, y as usize))
+            .filter(|&(x, y)| self.cells.cell(x, y).is_empty())
+            .collect();
+
+        territory_after_fill(
+            &self.cells,
+            &self.state.my_distance,
+            &self.state.opponent_distance,
+            self.state.territory,
+            &placed,
+        )
+        .margin()
+    }
+
+    // How many of the placed cells sit where the opponent is expected to grow next,
+    // in tenths of a cell
+    pub fn calculate_anticipation_score(&self, placement_x: i32, placement_y: i32) -> i32 {
+        let likelihood: f64 = self
+            .placed_cells(placement_x, placement_y)
+            .map(|(x, y)| {
+                self.opponent.expansion_likelihood(
+                    x as usize,
+                    y as usize,
+                    self.board_width,
+                    self.board_height,
+                )
+            })
+            .sum();
+
+        (likelihood * 10.0).round() as i32
+    }
+
+    // Combined weighted score of a placement for the current piece
+    pub fn score_placement(&self, x: i32, y: i32) -> i32 {
+        // Simplified, focused scoring - heat map is primary strategy
+        let heat_score = self.calculate_heat_score(x, y);
+        let blocking_score = self.calculate_blocking_score(x, y);
+        let expansion_score = self.calculate_expansion_score(x, y);
+        let efficiency_score = self.calculate_piece_efficiency(x, y);
+        // The BFS is the most expensive term, skip it when it does not count
+        let territory_score = if self.scoring.territory_weight != 0 {
+            self.calculate_territory_score(x, y)
+        } else {
+            0
+        };
+        let anticipation_score = if self.scoring.anticipation_weight != 0 {
+            self.calculate_anticipation_score(x, y)
+        } else {
+            0
+        };
+
+        // Heat map dominates with high weight, others provide fine-tuning
+        let weights = &self.scoring;
+        heat_score * weights.heat_weight // Primary: aggressive positioning
+            + blocking_score * weights.blocking_weight // Secondary: block opponent
+            + expansion_score * weights.expansion_weight // Tertiary: maintain options
+            + efficiency_score * weights.efficiency_weight // Bonus: piece size efficiency
+            + territory_score * weights.territory_weight // Cells we reach first
+            + anticipation_score * weights.anticipation_weight // Where the opponent grows next
+    }
+
+    // Scores the moves in order until the deadline passes
+    // Moves that were not reached score i32::MIN
+    pub fn score_placements_until(&self, moves: &[(i32, i32)], deadline: Deadline) -> Vec<i32> {
+        let mut scores = vec![i32::MIN; moves.len()];
+        for (score, &(x, y)) in scores.iter_mut().zip(moves) {
+            if deadline.expired() {
+                break;
             }
+            *score = self.score_placement(x, y);
         }
+        scores
+    }
 
-        solid_count * 2 // Bonus for placing larger pieces
+    // Whether the players are walled off from each other, even for the widest
+    // piece seen so far; never before the first board
+    pub fn is_endgame(&self) -> bool {
+        self.board_width > 0
+            && Separation::from_regions(&self.cells, &self.state.regions, self.piece_reach)
+                .is_endgame()
     }
 
-    pub fn find_best_move(&self) -> Option<(usize, usize)> {
+    // Highest scoring placement, or the best one scored before the deadline
+    // In the endgame the move that packs our own area best is played instead
+    pub fn find_best_move(&self) -> Option<(i32, i32)> {
         let valid_moves = self.find_all_valid_placements();
 
         if valid_moves.is_empty() {
             return None;
         }
 
+        if self.is_endgame() {
+            let frontier: Vec<(usize, usize)> = self.state.my_frontier().collect();
+            return Packer::default().best_move(
+                &self.cells,
+                &frontier,
+                &self.current_piece,
+                &valid_moves,
+                self.deadline,
+            );
+        }
+
         let mut best_move = None;
         let mut best_score = i32::MIN;
 
-        for &(x, y) in &valid_moves {
-            // Simplified, focused scoring - heat map is primary strategy
-            let heat_score = self.calculate_heat_score(x, y);
-            let blocking_score = self.calculate_blocking_score(x, y);
-            let expansion_score = self.calculate_expansion_score(x, y);
-            let efficiency_score = self.calculate_piece_efficiency(x, y);
-
-            // Heat map dominates with high weight, others provide fine-tuning
-            let total_score = heat_score * 100   // Primary: aggressive positioning
-                + blocking_score * 20            // Secondary: block opponent  
-                + expansion_score * 5            // Tertiary: maintain options
-                + efficiency_score * 10; // Bonus: piece size efficiency
-
+        let scores = self.score_placements_until(&valid_moves, self.deadline);
+        for (&(x, y), &total_score) in valid_moves.iter().zip(&scores) {
             if total_score > best_score {
                 best_score = total_score;
                 best_move = Some((x, y));
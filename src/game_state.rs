@@ -0,0 +1,187 @@
+use crate::board::{Board, Cell};
+use crate::distance::DistanceField;
+use crate::regions::RegionAnalysis;
+use crate::voronoi::{TerritoryControl, territory_from_fields};
+use std::collections::BTreeSet;
+
+// One cell that differs between two successive boards
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellChange {
+    pub x: usize,
+    pub y: usize,
+    pub before: Cell,
+    pub after: Cell,
+}
+
+impl CellChange {
+    // An empty cell that a player claimed; the only change a normal turn makes
+    // apart from last-placed marks turning into plain territory
+    pub fn is_fill(&self) -> bool {
+        self.before.is_empty() && !self.after.is_empty()
+    }
+
+    // Last-placed mark demoted to territory (or the reverse): ownership is unchanged
+    pub fn is_relabel(&self) -> bool {
+        (self.before.is_mine() && self.after.is_mine())
+            || (self.before.is_theirs() && self.after.is_theirs())
+    }
+}
+
+// Cells that differ between two boards of the same size, in row-major order
+// Returns None when the sizes differ
+pub fn diff_boards(before: &Board, after: &Board) -> Option<Vec<CellChange>> {
+    if before.width != after.width || before.height != after.height {
+        return None;
+    }
+    Some(
+        before
+            .iter()
+            .zip(after.iter())
+            .filter(|((_, _, old), (_, _, new))| old != new)
+            .map(|((x, y, old), (_, _, new))| CellChange {
+                x,
+                y,
+                before: old,
+                after: new,
+            })
+            .collect(),
+    )
+}
+
+// What one incremental update changed, for callers that patch their own copies
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TurnChanges {
+    // Cells a player claimed this turn, in row-major order
+    pub filled: Vec<CellChange>,
+    // Cells whose opponent distance changed
+    pub distance_changed: Vec<(usize, usize)>,
+}
+
+// Structures derived from the board that survive between turns
+// Each turn only the cells that changed are fed through: distance fields are
+// patched locally, frontiers rechecked around the changes and only the regions
+// that lost cells are flooded again
+#[derive(Clone, Debug, Default)]
+pub struct GameState {
+    pub width: usize,
+    pub height: usize,
+    // BFS distance from the opponent's cells through empty cells
+    pub opponent_distance: DistanceField,
+    // BFS distance from our cells through empty cells
+    pub my_distance: DistanceField,
+    // Empty regions and who can still reach them
+    pub regions: RegionAnalysis,
+    // Voronoi split of the empty cells, read off the two distance fields
+    pub territory: TerritoryControl,
+    // Owned cells with an empty neighbour (diagonals included), row-major indices
+    my_frontier: BTreeSet<usize>,
+    their_frontier: BTreeSet<usize>,
+}
+
+impl GameState {
+    // Builds every structure from scratch
+    pub fn new(board: &Board) -> Self {
+        let mut state = Self {
+            width: board.width,
+            height: board.height,
+            opponent_distance: DistanceField::compute(
+                board.width,
+                board.height,
+                |x, y| board.cell(x, y).is_theirs(),
+                |x, y| board.cell(x, y).is_empty(),
+            ),
+            my_distance: DistanceField::compute(
+                board.width,
+                board.height,
+                |x, y| board.cell(x, y).is_mine(),
+                |x, y| board.cell(x, y).is_empty(),
+            ),
+            regions: RegionAnalysis::compute(board),
+            territory: TerritoryControl::default(),
+            my_frontier: BTreeSet::new(),
+            their_frontier: BTreeSet::new(),
+        };
+        for (x, y, _) in board.iter() {
+            state.refresh_frontier(board, x, y);
+        }
+        state.territory =
+            territory_from_fields(board, &state.my_distance, &state.opponent_distance);
+        state
+    }
+
+    // Brings the state from `previous` to `board`
+    // Returns what the turn changed, or None when the state had to be rebuilt (new
+    // board size, or a change a normal turn cannot make)
+    pub fn update(&mut self, previous: &Board, board: &Board) -> Option<TurnChanges> {
+        let changes = diff_boards(previous, board)
+            .filter(|_| self.width == board.width && self.height == board.height)
+            .filter(|changes| changes.iter().all(|c| c.is_fill() || c.is_relabel()));
+        let Some(changes) = changes else {
+            *self = Self::new(board);
+            return None;
+        };
+
+        let fills: Vec<CellChange> = changes.into_iter().filter(CellChange::is_fill).collect();
+        let filled: Vec<(usize, usize)> = fills.iter().map(|change| (change.x, change.y)).collect();
+        let (mine, theirs): (Vec<_>, Vec<_>) = filled
+            .iter()
+            .partition(|&&(x, y)| board.cell(x, y).is_mine());
+
+        let is_empty = |x: usize, y: usize| board.cell(x, y).is_empty();
+
+        // Walls first (distances grow), then new sources (distances shrink)
+        let mut changed =
+            self.opponent_distance
+                .close(&mine, |x, y| board.cell(x, y).is_theirs(), is_empty);
+        changed.extend(self.opponent_distance.add_sources(&theirs, is_empty));
+        self.my_distance
+            .close(&theirs, |x, y| board.cell(x, y).is_mine(), is_empty);
+        self.my_distance.add_sources(&mine, is_empty);
+
+        self.regions.fill(board, &filled);
+        for &(x, y) in &filled {
+            for (nx, ny, _) in board.neighbours(x as i32, y as i32) {
+                self.refresh_frontier(board, nx as usize, ny as usize);
+            }
+            self.refresh_frontier(board, x, y);
+        }
+        self.territory = territory_from_fields(board, &self.my_distance, &self.opponent_distance);
+
+        Some(TurnChanges {
+            filled: fills,
+            distance_changed: changed,
+        })
+    }
+
+    // Our owned cells that touch an empty cell, in row-major order
+    pub fn my_frontier(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.my_frontier
+            .iter()
+            .map(|&index| (index % self.width, index / self.width))
+    }
+
+    // The opponent's owned cells that touch an empty cell, in row-major order
+    pub fn their_frontier(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.their_frontier
+            .iter()
+            .map(|&index| (index % self.width, index / self.width))
+    }
+
+    fn refresh_frontier(&mut self, board: &Board, x: usize, y: usize) {
+        let index = y * self.width + x;
+        let cell = board.cell(x, y);
+        let touches_empty = || {
+            board
+                .neighbours(x as i32, y as i32)
+                .any(|(_, _, neighbour)| neighbour.is_empty())
+        };
+
+        self.my_frontier.remove(&index);
+        self.their_frontier.remove(&index);
+        if cell.is_mine() && touches_empty() {
+            self.my_frontier.insert(index);
+        } else if cell.is_theirs() && touches_empty() {
+            self.their_frontier.insert(index);
+        }
+    }
+}
@@ -1,7 +1,31 @@
-
+pub mod bitboard;
+pub mod board;
+pub mod config;
+pub mod deadline;
+pub mod distance;
+pub mod endgame;
 pub mod filler_ai;
+pub mod game_state;
+pub mod map;
+pub mod mcts;
+pub mod opponent;
+pub mod options;
+pub mod overlay;
+pub mod palette;
 pub mod piece;
 pub mod player;
-pub mod utils;
+pub mod protocol;
+pub mod referee;
+pub mod regions;
+pub mod replay;
+pub mod rng;
+pub mod rules;
+pub mod search;
+pub mod strategy;
+pub mod tui;
+pub mod tuning;
+pub mod voronoi;
 #[cfg(feature = "visualizer")]
-pub mod visualizer;
\ No newline at end of file
+pub mod visualizer;
+#[cfg(feature = "export")]
+pub mod export;
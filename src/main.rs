@@ -1,78 +1,131 @@
-mod filler_ai;
-mod piece;
-mod player;
-mod utils;
-#[cfg(feature = "visualizer")]
-mod visualizer;
-use std::io::{self, BufRead};
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+use std::time::Instant;
 
-use crate::filler_ai::FillerAi;
-use crate::piece::*;
-use crate::utils::*;
+use filler::deadline::Deadline;
+use filler::filler_ai::FillerAi;
+use filler::options::Options;
+use filler::protocol::{ProtocolError, ProtocolReader};
+use filler::replay::ReplayWriter;
+use filler::strategy::{Strategy, strategy_from_name};
+use filler::tui::TuiRenderer;
+#[cfg(feature = "visualizer")]
+use filler::visualizer::get_visualizer;
 
 fn main() {
+    let options = Options::from_env().unwrap_or_else(|e| {
+        eprintln!("filler: {}", e);
+        process::exit(2);
+    });
+    let mut strategy = strategy_from_name(&options.strategy).expect("strategy name is validated");
+
     let stdin = io::stdin();
-    let mut lines = stdin.lock().lines();
+    let mut reader = ProtocolReader::new(stdin.lock());
+
+    // Malformed input is reported on stderr instead of being answered with a guess
+    if let Err(e) = run(&mut reader, &options, strategy.as_mut()) {
+        eprintln!("filler: protocol error: {}", e);
+        process::exit(1);
+    }
+}
+
+// Plays turns until the engine closes the input
+fn run<R: BufRead>(
+    reader: &mut ProtocolReader<R>,
+    options: &Options,
+    strategy: &mut dyn Strategy,
+) -> Result<(), ProtocolError> {
+    let player_number = reader.read_player_number()?;
+    let mut ai = FillerAi::new(player_number);
+    ai.scoring = options.scoring.clone();
 
-    if let Some(Ok(player_line)) = lines.next() {
-        let player_number = if player_line.contains("p1") { 1 } else { 2 };
+    let mut replay = options
+        .replay
+        .as_deref()
+        .and_then(|path| open_replay(path, player_number));
+    let mut tui = options.tui.as_deref().and_then(open_tui);
 
-        let mut ai = FillerAi::new(player_number);
+    while let Some(turn) = reader.read_turn()? {
+        // The engine's clock is running from here
+        let started = Instant::now();
+        ai.deadline = Deadline::after(options.move_time);
+        ai.update_board(turn.width, turn.height, turn.board)
+            .map_err(|e| ProtocolError {
+                line: reader.line_number(),
+                expected: "a board of engine cells".to_string(),
+                actual: e,
+            })?;
+        ai.update_piece(turn.piece);
 
-        loop {
-            // Parse board header
-            if let Some(Ok(board_header)) = lines.next() {
-                if let Some((width, height)) = parse_board_header(&board_header) {
-                    // Skip column number line
-                    if let Some(Ok(_col_line)) = lines.next() {}
+        // Coordinates are signed: the piece may hang off the top/left edge
+        let answer = strategy.choose_move(&ai);
+        let elapsed = started.elapsed();
+        if let Some((move_x, move_y)) = answer {
+            println!("{} {}", move_x, move_y);
+        } else {
+            println!("0 0");
+        }
 
-                    // Parse board data
-                    let mut board = Vec::new();
-                    for _ in 0..height {
-                        if let Some(Ok(board_row)) = lines.next() {
-                            // Extract actual board data (skip row number prefix)
-                            if let Some(space_pos) = board_row.find(' ') {
-                                let row_data: Vec<char> =
-                                    board_row[(space_pos + 1)..].chars().collect();
-                                board.push(row_data);
-                            }
-                        }
-                    }
+        #[cfg(feature = "visualizer")]
+        show_turn(&ai, answer);
+
+        if let Some(renderer) = tui.as_mut()
+            && let Err(e) =
+                renderer.draw(&ai.cells, player_number, &ai.current_piece, answer, elapsed)
+        {
+            eprintln!("filler: terminal view stopped: {}", e);
+            tui = None;
+        }
 
-                    // Update AI with board data
-                    ai.update_board(width, height, board);
+        // Recorded after answering so the engine never waits on the disk
+        if let Some(writer) = replay.as_mut()
+            && let Err(e) = writer.record(
+                &ai.cells.to_chars(&ai.my_player, &ai.opponent_player),
+                &ai.current_piece,
+                answer,
+                elapsed,
+            )
+        {
+            eprintln!("filler: replay stopped: {}", e);
+            replay = None;
+        }
+    }
 
-                    // Parse piece header
-                    if let Some(Ok(piece_header)) = lines.next() {
-                        if let Some((piece_width, piece_height)) = parse_piece_header(&piece_header)
-                        {
-                            // Parse piece pattern
-                            let mut piece_pattern = Vec::new();
-                            for _ in 0..piece_height {
-                                if let Some(Ok(piece_row)) = lines.next() {
-                                    let pattern_row: Vec<char> = piece_row.chars().collect();
-                                    piece_pattern.push(pattern_row);
-                                }
-                            }
+    Ok(())
+}
 
-                            // Create piece and update AI
-                            let piece = Piece::new(piece_width, piece_height, piece_pattern);
-                            ai.update_piece(piece);
+// A replay that cannot be written only costs the recording, never the game
+fn open_replay(path: &str, player_number: u8) -> Option<ReplayWriter<BufWriter<File>>> {
+    match ReplayWriter::create(path, player_number) {
+        Ok(writer) => Some(writer),
+        Err(e) => {
+            eprintln!("filler: replay disabled: {}: {}", path, e);
+            None
+        }
+    }
+}
 
-                            // Make a move (placeholder logic for now)
-                            if let Some((move_x, move_y)) = ai.find_best_move() {
-                                println!("{} {}", move_x, move_y);
-                            } else {
-                                println!("0 0");
-                            }
-                        }
-                    }
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
+// Like the replay, a terminal view that cannot be opened never stops the game
+fn open_tui(target: &str) -> Option<TuiRenderer<Box<dyn Write + Send>>> {
+    match TuiRenderer::open(target) {
+        Ok(renderer) => Some(renderer),
+        Err(e) => {
+            eprintln!("filler: terminal view disabled: {}: {}", target, e);
+            None
         }
     }
 }
+
+// Sends the board to the visualizer, or a copy of the AI when the window shows
+// the heat and score overlay; scoring every candidate for it happens on the
+// visualizer thread, never against the engine's clock
+#[cfg(feature = "visualizer")]
+fn show_turn(ai: &FillerAi, answer: Option<(i32, i32)>) {
+    let visualizer = get_visualizer();
+    if visualizer.wants_overlay() {
+        visualizer.update_turn(ai.clone(), answer);
+    } else {
+        visualizer.update_board(&ai.cells, ai.my_player.number);
+    }
+}
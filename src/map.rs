@@ -0,0 +1,250 @@
+use crate::board::{Board, Cell};
+use crate::rng::Rng;
+use std::fs;
+
+// Names of the maps bundled with the crate, in increasing size
+pub const STANDARD_MAPS: &[&str] = &["map00", "map01", "map02"];
+
+// A Filler map: board dimensions plus the starting cell of each player
+//
+// File format, one directive per line ('#' starts a comment):
+//     size <width> <height>
+//     @ <x> <y>
+//     $ <x> <y>
+// Engine-style maps (rows of '.', '@' and '$') are accepted as well
+#[derive(Clone, Debug, PartialEq)]
+pub struct Map {
+    pub width: usize,
+    pub height: usize,
+    // Starting cells for player 1 ('@') and player 2 ('$')
+    pub starts: [(usize, usize); 2],
+}
+
+impl Map {
+    // Parses a map from either the directive format or an engine-style grid
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.split('#').next().unwrap_or("").trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+
+        let Some(&(_, first)) = lines.first() else {
+            return Err("map is empty".to_string());
+        };
+
+        let map = if first.starts_with("size") {
+            Self::parse_directives(&lines)?
+        } else {
+            Self::parse_grid(&lines)?
+        };
+        map.validate()?;
+        Ok(map)
+    }
+
+    // Reads a map file from disk
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // Returns one of the bundled maps by name
+    pub fn standard(name: &str) -> Option<Self> {
+        let text = match name {
+            "map00" => include_str!("../maps/map00"),
+            "map01" => include_str!("../maps/map01"),
+            "map02" => include_str!("../maps/map02"),
+            _ => return None,
+        };
+        Self::parse(text).ok()
+    }
+
+    // Builds the board grid in the same shape FillerAi::update_board receives
+    pub fn to_board(&self) -> Vec<Vec<char>> {
+        let mut board = vec![vec!['.'; self.width]; self.height];
+        let [(x1, y1), (x2, y2)] = self.starts;
+        board[y1][x1] = '@';
+        board[y2][x2] = '$';
+        board
+    }
+
+    // Typed starting board seen from player 1
+    pub fn to_cells(&self) -> Board {
+        let mut board = Board::new(self.width, self.height);
+        let [(x1, y1), (x2, y2)] = self.starts;
+        board.set(x1, y1, Cell::Mine);
+        board.set(x2, y2, Cell::Theirs);
+        board
+    }
+
+    // Parses "size", "@" and "$" directives
+    fn parse_directives(lines: &[(usize, &str)]) -> Result<Self, String> {
+        let mut size = None;
+        let mut starts = [None, None];
+
+        for &(line_number, line) in lines {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let values = parse_pair(&parts[1..]).ok_or_else(|| {
+                format!("line {}: expected two numbers in '{}'", line_number, line)
+            })?;
+
+            match parts[0] {
+                "size" => size = Some(values),
+                "@" => starts[0] = Some(values),
+                "$" => starts[1] = Some(values),
+                other => {
+                    return Err(format!(
+                        "line {}: unknown directive '{}'",
+                        line_number, other
+                    ));
+                }
+            }
+        }
+
+        let (width, height) = size.ok_or("missing 'size' line")?;
+        Ok(Self {
+            width,
+            height,
+            starts: [
+                starts[0].ok_or("missing '@' start")?,
+                starts[1].ok_or("missing '$' start")?,
+            ],
+        })
+    }
+
+    // Parses an engine-style grid with exactly one '@' and one '$'
+    fn parse_grid(lines: &[(usize, &str)]) -> Result<Self, String> {
+        let width = lines[0].1.chars().count();
+        let mut starts = [None, None];
+
+        for (y, &(line_number, line)) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(format!(
+                    "line {}: row width differs from first row ({})",
+                    line_number, width
+                ));
+            }
+            for (x, cell) in line.chars().enumerate() {
+                let index = match cell {
+                    '.' => continue,
+                    '@' => 0,
+                    '$' => 1,
+                    other => {
+                        return Err(format!("line {}: unexpected cell '{}'", line_number, other));
+                    }
+                };
+                if starts[index].replace((x, y)).is_some() {
+                    return Err(format!(
+                        "line {}: more than one '{}' start",
+                        line_number, cell
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: lines.len(),
+            starts: [
+                starts[0].ok_or("missing '@' start")?,
+                starts[1].ok_or("missing '$' start")?,
+            ],
+        })
+    }
+
+    // Checks that both starts are distinct cells on the board
+    fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err("board must be at least 1x1".to_string());
+        }
+        for (symbol, &(x, y)) in ['@', '$'].iter().zip(&self.starts) {
+            if x >= self.width || y >= self.height {
+                return Err(format!(
+                    "'{}' start ({}, {}) is outside the board",
+                    symbol, x, y
+                ));
+            }
+        }
+        if self.starts[0] == self.starts[1] {
+            return Err("both players start on the same cell".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Settings for procedural maps
+#[derive(Clone, Debug)]
+pub struct MapGeneratorConfig {
+    pub min_width: usize,
+    pub max_width: usize,
+    pub min_height: usize,
+    pub max_height: usize,
+    // Minimum Manhattan distance between the two starts, as a fraction of width + height
+    pub min_separation: f64,
+}
+
+impl Default for MapGeneratorConfig {
+    fn default() -> Self {
+        Self {
+            min_width: 15,
+            max_width: 100,
+            min_height: 15,
+            max_height: 100,
+            min_separation: 0.4,
+        }
+    }
+}
+
+// Generates a random map; the same config and seed always give the same map
+pub fn generate_map(config: &MapGeneratorConfig, seed: u64) -> Map {
+    let mut rng = Rng::new(seed);
+    let width = rng.range(
+        config.min_width.max(2),
+        config.max_width.max(config.min_width.max(2)),
+    );
+    let height = rng.range(
+        config.min_height.max(1),
+        config.max_height.max(config.min_height.max(1)),
+    );
+    let min_distance = ((width + height) as f64 * config.min_separation) as usize;
+
+    let first = (rng.range(0, width - 1), rng.range(0, height - 1));
+    let mut second = first;
+    let mut best_distance = 0;
+
+    // Sample second starts until one is far enough, keeping the farthest as a fallback
+    for _ in 0..100 {
+        let candidate = (rng.range(0, width - 1), rng.range(0, height - 1));
+        let distance = first.0.abs_diff(candidate.0) + first.1.abs_diff(candidate.1);
+        if distance > best_distance {
+            best_distance = distance;
+            second = candidate;
+        }
+        if best_distance >= min_distance {
+            break;
+        }
+    }
+
+    // Degenerate boards can fail to sample a distinct cell; use the opposite corner
+    if second == first {
+        second = (width - 1 - first.0, height - 1 - first.1);
+        if second == first {
+            second = ((first.0 + 1) % width, first.1);
+        }
+    }
+
+    Map {
+        width,
+        height,
+        starts: [first, second],
+    }
+}
+
+// Parses exactly two unsigned integers
+fn parse_pair(parts: &[&str]) -> Option<(usize, usize)> {
+    match parts {
+        [a, b] => Some((a.parse().ok()?, b.parse().ok()?)),
+        _ => None,
+    }
+}
@@ -0,0 +1,326 @@
+use std::time::Duration;
+
+use crate::bitboard::CompiledPiece;
+use crate::board::Side;
+use crate::deadline::Deadline;
+use crate::filler_ai::FillerAi;
+use crate::piece::{Piece, PieceGenerator, PieceGeneratorConfig};
+use crate::rng::Rng;
+use crate::rules::Position;
+use crate::search::{evaluate, order_by_score};
+use crate::strategy::Strategy;
+
+// Monte Carlo Tree Search over placements of sampled pieces
+// Nodes alternate between the players. The root holds our current piece; every other
+// node samples a piece for its side when it is expanded, and its children are
+// placements of that piece. Each iteration walks down by UCT, expands one untried
+// placement, plays random pieces at random legal offsets for a few plies and
+// backpropagates the result up the path. The end position counts as a win, draw or
+// loss by the sign of the alpha-beta evaluation. The root move with the most visits
+// wins once the time budget or iteration cap is reached.
+pub struct MonteCarloSearch {
+    // Cap on the lookahead time; the AI's move deadline bounds the whole move
+    pub time_budget: Duration,
+    pub max_iterations: usize,
+    // Plies simulated after the expanded node before the position is judged
+    pub playout_depth: usize,
+    // UCT exploration constant
+    pub exploration: f64,
+    // Only the most promising root moves (by heuristic score) are searched
+    pub max_root_moves: usize,
+    // Placements kept per node below the root, picked at random from the legal ones
+    pub max_replies: usize,
+    pub seed: u64,
+}
+
+impl Default for MonteCarloSearch {
+    fn default() -> Self {
+        Self {
+            time_budget: Duration::from_millis(500),
+            max_iterations: usize::MAX,
+            playout_depth: 8,
+            exploration: 1.4,
+            max_root_moves: 16,
+            max_replies: 8,
+            seed: 0,
+        }
+    }
+}
+
+// One position in the tree, reached by `placement` from its parent
+#[derive(Clone, Debug)]
+pub struct Node {
+    // None at the root
+    pub placement: Option<(i32, i32)>,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    // Side placing `piece` from this position
+    pub mover: Side,
+    pub piece: Piece,
+    compiled: Option<CompiledPiece>,
+    // Placements not expanded yet, in the order they will be tried
+    untried: Vec<(i32, i32)>,
+    pub visits: u32,
+    // Wins for the side that moved into this node; wins count 1, draws 0.5
+    pub wins: f64,
+}
+
+impl Node {
+    fn new(
+        parent: Option<usize>,
+        placement: Option<(i32, i32)>,
+        mover: Side,
+        piece: Piece,
+        untried: Vec<(i32, i32)>,
+    ) -> Self {
+        let compiled = CompiledPiece::new(&piece);
+        Self {
+            placement,
+            parent,
+            children: Vec::new(),
+            mover,
+            piece,
+            compiled,
+            untried,
+            visits: 0,
+            wins: 0.0,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.wins / self.visits as f64
+        }
+    }
+}
+
+// Visit and win counts for one root move
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoveStats {
+    pub placement: (i32, i32),
+    pub visits: u32,
+    // Wins count 1, draws 0.5
+    pub wins: f64,
+}
+
+impl MoveStats {
+    pub fn win_rate(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.wins / self.visits as f64
+        }
+    }
+}
+
+impl MonteCarloSearch {
+    // Picks a move for the AI's current board and piece
+    // Returns None only when the current piece has no legal placement
+    pub fn find_move(&self, ai: &FillerAi) -> Option<(i32, i32)> {
+        // Separated players do not interact; the packing move beats random playouts
+        if ai.is_endgame() {
+            return ai.find_best_move();
+        }
+
+        // max_by keeps the last of equal elements, so the list is walked backwards to
+        // break ties towards the better ordered move; when the deadline leaves no
+        // iteration at all, that is the heuristic's own choice
+        let stats = self.run(ai);
+        stats
+            .iter()
+            .rev()
+            .max_by(|a, b| {
+                a.visits
+                    .cmp(&b.visits)
+                    .then(a.win_rate().total_cmp(&b.win_rate()))
+            })
+            .map(|best| best.placement)
+    }
+
+    // Runs the search and returns the statistics for every root move, best ordered first
+    pub fn run(&self, ai: &FillerAi) -> Vec<MoveStats> {
+        let nodes = self.search(ai);
+        let root = &nodes[0];
+
+        let expanded = root.children.iter().map(|&child| MoveStats {
+            placement: nodes[child].placement.unwrap_or_default(),
+            visits: nodes[child].visits,
+            wins: nodes[child].wins,
+        });
+        let unexpanded = root.untried.iter().map(|&placement| MoveStats {
+            placement,
+            visits: 0,
+            wins: 0.0,
+        });
+        expanded.chain(unexpanded).collect()
+    }
+
+    // Grows the tree until the time budget or iteration cap and returns its nodes,
+    // the root first
+    pub fn search(&self, ai: &FillerAi) -> Vec<Node> {
+        let mut root_moves = order_by_score(ai, ai.find_all_valid_placements(), ai.deadline);
+        root_moves.truncate(self.max_root_moves.max(1));
+
+        let mut nodes = vec![Node::new(
+            None,
+            None,
+            Side::Me,
+            ai.current_piece.clone(),
+            root_moves,
+        )];
+        if nodes[0].untried.is_empty() {
+            return nodes;
+        }
+
+        let deadline = Deadline::after(self.time_budget).min(ai.deadline);
+        let mut rng = Rng::new(self.seed);
+        let mut pieces = PieceGenerator::new(
+            PieceGeneratorConfig::for_board(ai.board_width, ai.board_height),
+            self.seed,
+        );
+
+        let mut iteration = 0;
+        while iteration < self.max_iterations && !deadline.expired() {
+            iteration += 1;
+            let mut position = ai.position();
+
+            // Selection: descend through fully expanded nodes
+            let mut current = 0;
+            while nodes[current].untried.is_empty() && !nodes[current].children.is_empty() {
+                let child = self.select(&nodes, current);
+                Self::apply(&nodes, current, child, &mut position);
+                current = child;
+            }
+
+            // Expansion: the root's moves go in heuristic order
+            if !nodes[current].untried.is_empty() {
+                let placement = nodes[current].untried.remove(0);
+                let mover = nodes[current].mover.other();
+                let piece = pieces.next_piece();
+                let child = nodes.len();
+                nodes.push(Node::new(
+                    Some(current),
+                    Some(placement),
+                    mover,
+                    piece,
+                    Vec::new(),
+                ));
+                nodes[current].children.push(child);
+                Self::apply(&nodes, current, child, &mut position);
+
+                let mut replies = position.valid_placements(
+                    &nodes[child].piece,
+                    nodes[child].compiled.as_ref(),
+                    mover,
+                );
+                while replies.len() > self.max_replies.max(1) {
+                    replies.swap_remove(rng.range(0, replies.len() - 1));
+                }
+                nodes[child].untried = replies;
+                current = child;
+            }
+
+            // Simulation: a node without placements means its side is out
+            let mover = nodes[current].mover;
+            let stuck = nodes[current].untried.is_empty() && nodes[current].children.is_empty();
+            let result = self.playout(&mut position, mover, stuck, &mut pieces, &mut rng);
+
+            // Backpropagation, each node scored for the side that moved into it
+            let mut node = Some(current);
+            while let Some(index) = node {
+                let moved = nodes[index].mover.other();
+                nodes[index].visits += 1;
+                nodes[index].wins += if moved == Side::Me {
+                    result
+                } else {
+                    1.0 - result
+                };
+                node = nodes[index].parent;
+            }
+        }
+
+        nodes
+    }
+
+    // Plays the child's placement, the parent's piece for the parent's side
+    fn apply(nodes: &[Node], parent: usize, child: usize, position: &mut Position) {
+        let (parent, child) = (&nodes[parent], &nodes[child]);
+        if let Some((x, y)) = child.placement {
+            position.place(&parent.piece, parent.compiled.as_ref(), x, y, parent.mover);
+        }
+    }
+
+    // UCT among the children of a fully expanded node
+    fn select(&self, nodes: &[Node], parent: usize) -> usize {
+        let log_total = (nodes[parent].visits.max(1) as f64).ln();
+        let uct = |&child: &usize| {
+            let node = &nodes[child];
+            node.win_rate() + self.exploration * (log_total / node.visits.max(1) as f64).sqrt()
+        };
+        nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .max_by(|a, b| uct(a).total_cmp(&uct(b)))
+            .unwrap_or(parent)
+    }
+
+    // Plays random legal moves starting with `first`, then judges the position
+    // `first_out` marks a side that already has no legal placement
+    // Returns 1 for a win, 0.5 for a draw and 0 for a loss from our point of view
+    fn playout(
+        &self,
+        position: &mut Position,
+        first: Side,
+        first_out: bool,
+        pieces: &mut PieceGenerator,
+        rng: &mut Rng,
+    ) -> f64 {
+        // Same as the real game: a player with no legal placement is out
+        let mut active = [!first_out, true];
+        let mut mover = first;
+
+        for ply in 0..self.playout_depth {
+            let side = ply % 2; // 0 = first
+            if !active[side] {
+                if !active[1 - side] {
+                    break;
+                }
+                mover = mover.other();
+                continue;
+            }
+
+            let piece = pieces.next_piece();
+            let compiled = CompiledPiece::new(&piece);
+            let moves = position.valid_placements(&piece, compiled.as_ref(), mover);
+            if moves.is_empty() {
+                active[side] = false;
+            } else {
+                let (x, y) = moves[rng.range(0, moves.len() - 1)];
+                position.place(&piece, compiled.as_ref(), x, y, mover);
+            }
+            mover = mover.other();
+        }
+
+        match evaluate(&position.board) {
+            score if score > 0 => 1.0,
+            0 => 0.5,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Strategy for MonteCarloSearch {
+    fn name(&self) -> &'static str {
+        "mcts"
+    }
+
+    // Advances the seed every turn so playouts differ between moves
+    fn choose_move(&mut self, ai: &FillerAi) -> Option<(i32, i32)> {
+        let chosen = self.find_move(ai);
+        self.seed = self.seed.wrapping_add(1);
+        chosen
+    }
+}
@@ -0,0 +1,199 @@
+use crate::board::Board;
+
+// The board is split into REGION_GRID x REGION_GRID areas for region preferences
+pub const REGION_GRID: usize = 3;
+// Recent moves weigh more: each older move counts this much less in the growth direction
+const DIRECTION_DECAY: f64 = 0.7;
+
+// One opponent placement recovered from two successive boards
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpponentMove {
+    // Number of boards observed before this move was seen
+    pub turn: usize,
+    // Board cells the placement claimed
+    pub cells: Vec<(usize, usize)>,
+    // Centre of the placed cells
+    pub centroid: (f64, f64),
+    // Centre of the opponent's territory before the placement
+    pub origin: (f64, f64),
+    // Placed cells touching our territory, diagonals included
+    pub contact_cells: usize,
+}
+
+impl OpponentMove {
+    // Offset from the old territory centre to the placed cells
+    pub fn growth(&self) -> (f64, f64) {
+        (
+            self.centroid.0 - self.origin.0,
+            self.centroid.1 - self.origin.1,
+        )
+    }
+}
+
+// History of the opponent's placements and what they say about its play
+// Fed the opponent cells each turn filled, which make up one move
+#[derive(Clone, Debug, Default)]
+pub struct OpponentModel {
+    pub moves: Vec<OpponentMove>,
+    turns: usize,
+    // Placed cells per region, row-major over the REGION_GRID x REGION_GRID areas
+    region_cells: [usize; REGION_GRID * REGION_GRID],
+    // Cached growth_direction, refreshed whenever a move is recorded
+    direction: Option<(f64, f64)>,
+}
+
+impl OpponentModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Records the opponent cells filled this turn on `board` as one move
+    // The caller diffs the boards; on the first board it passes the last-placed cells
+    pub fn observe(&mut self, board: &Board, placed: &[(usize, usize)]) {
+        if let Some(placed_centroid) = centroid(placed.iter().copied()) {
+            // The territory before the placement is everything else they own
+            let origin = centroid(
+                board
+                    .iter()
+                    .filter(|&(x, y, cell)| cell.is_theirs() && !placed.contains(&(x, y)))
+                    .map(|(x, y, _)| (x, y)),
+            );
+            let contact_cells = placed
+                .iter()
+                .filter(|&&(x, y)| {
+                    board
+                        .neighbours(x as i32, y as i32)
+                        .any(|(_, _, cell)| cell.is_mine())
+                })
+                .count();
+
+            for &(x, y) in placed {
+                self.region_cells[region_index(x, y, board.width, board.height)] += 1;
+            }
+            self.moves.push(OpponentMove {
+                turn: self.turns,
+                centroid: placed_centroid,
+                // A first move with no earlier territory has no direction
+                origin: origin.unwrap_or(placed_centroid),
+                cells: placed.to_vec(),
+                contact_cells,
+            });
+            self.direction = self.decayed_direction();
+        }
+
+        self.turns += 1;
+    }
+
+    pub fn last_move(&self) -> Option<&OpponentMove> {
+        self.moves.last()
+    }
+
+    // Unit vector of the direction the opponent has been growing in
+    // Recent moves weigh more; None until a move with a clear direction is seen
+    pub fn growth_direction(&self) -> Option<(f64, f64)> {
+        self.direction
+    }
+
+    // Share of all placed opponent cells that landed in the region holding (x, y)
+    pub fn region_preference(&self, x: usize, y: usize, width: usize, height: usize) -> f64 {
+        let total: usize = self.region_cells.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        self.region_cells[region_index(x, y, width, height)] as f64 / total as f64
+    }
+
+    // Region (column, row) of the REGION_GRID x REGION_GRID grid with the most placed cells
+    pub fn preferred_region(&self) -> Option<(usize, usize)> {
+        let (index, &count) = self
+            .region_cells
+            .iter()
+            .enumerate()
+            .max_by_key(|&(index, &count)| (count, std::cmp::Reverse(index)))?;
+        (count > 0).then_some((index % REGION_GRID, index / REGION_GRID))
+    }
+
+    // Fraction of placed cells that touched our territory, from 0 (avoids us) to 1
+    pub fn aggression(&self) -> f64 {
+        let placed: usize = self.moves.iter().map(|m| m.cells.len()).sum();
+        if placed == 0 {
+            return 0.0;
+        }
+        let contact: usize = self.moves.iter().map(|m| m.contact_cells).sum();
+        contact as f64 / placed as f64
+    }
+
+    // How likely the opponent is to expand into (x, y) next, from 0 to 1
+    // Cells near the last placement, ahead of the growth direction and in the
+    // regions the opponent favours score highest
+    pub fn expansion_likelihood(&self, x: usize, y: usize, width: usize, height: usize) -> f64 {
+        let Some(last) = self.last_move() else {
+            return 0.0;
+        };
+
+        let distance = last
+            .cells
+            .iter()
+            .map(|&(cx, cy)| cx.abs_diff(x) + cy.abs_diff(y))
+            .min()
+            .unwrap_or(0);
+        let proximity = 1.0 / (1.0 + distance as f64 / 4.0);
+
+        let alignment = match self.growth_direction() {
+            Some((gx, gy)) => {
+                let (dx, dy) = (x as f64 - last.centroid.0, y as f64 - last.centroid.1);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length < 1e-9 {
+                    1.0
+                } else {
+                    ((dx * gx + dy * gy) / length).max(0.0)
+                }
+            }
+            None => 0.5,
+        };
+
+        // Region count relative to the favourite region, so the favourite scores 1
+        let max_region = self.region_cells.iter().copied().max().unwrap_or(0);
+        let region_strength = if max_region == 0 {
+            0.0
+        } else {
+            self.region_cells[region_index(x, y, width, height)] as f64 / max_region as f64
+        };
+
+        proximity * (0.5 + 0.5 * alignment) * (0.5 + 0.5 * region_strength)
+    }
+
+    fn decayed_direction(&self) -> Option<(f64, f64)> {
+        let mut weight = 1.0;
+        let (mut dx, mut dy) = (0.0, 0.0);
+        for opponent_move in self.moves.iter().rev() {
+            let (gx, gy) = opponent_move.growth();
+            dx += gx * weight;
+            dy += gy * weight;
+            weight *= DIRECTION_DECAY;
+        }
+
+        let length = (dx * dx + dy * dy).sqrt();
+        if length < 1e-9 {
+            None
+        } else {
+            Some((dx / length, dy / length))
+        }
+    }
+}
+
+fn centroid(cells: impl Iterator<Item = (usize, usize)>) -> Option<(f64, f64)> {
+    let (mut sum_x, mut sum_y, mut count) = (0.0, 0.0, 0usize);
+    for (x, y) in cells {
+        sum_x += x as f64;
+        sum_y += y as f64;
+        count += 1;
+    }
+    (count > 0).then(|| (sum_x / count as f64, sum_y / count as f64))
+}
+
+fn region_index(x: usize, y: usize, width: usize, height: usize) -> usize {
+    let column = (x * REGION_GRID / width.max(1)).min(REGION_GRID - 1);
+    let row = (y * REGION_GRID / height.max(1)).min(REGION_GRID - 1);
+    row * REGION_GRID + column
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use crate::config::ScoringConfig;
+use crate::strategy::STRATEGY_NAMES;
+
+// Per-move time when neither --time nor FILLER_MOVE_TIME_MS is given
+// Well under the engine's default 10 s timeout
+pub const DEFAULT_MOVE_TIME_MS: u64 = 2000;
+
+// Runtime options for the AI binary
+// Command line flags take precedence over environment variables, which take
+// precedence over the defaults. The engine starts the binary without arguments,
+// so every option can also be set through the environment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    // --strategy <name> or FILLER_STRATEGY
+    pub strategy: String,
+    // --config <path> or FILLER_CONFIG, then FILLER_<KEY> overrides per weight
+    pub scoring: ScoringConfig,
+    // --time <ms> or FILLER_MOVE_TIME_MS: deadline for each move, counted from
+    // the moment the turn has been read
+    pub move_time: Duration,
+    // --replay <path> or FILLER_REPLAY: file (or directory, one file per game)
+    // that every turn and our answer are recorded to
+    pub replay: Option<String>,
+    // --tui <target> or FILLER_TUI: draw each turn in the terminal, to stderr
+    // ("stderr" or "-") or to a file
+    pub tui: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            strategy: STRATEGY_NAMES[0].to_string(),
+            scoring: ScoringConfig::default(),
+            move_time: Duration::from_millis(DEFAULT_MOVE_TIME_MS),
+            replay: None,
+            tui: None,
+        }
+    }
+}
+
+impl Options {
+    // Reads options from the process arguments and environment
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1), |key| std::env::var(key).ok())
+    }
+
+    // Builds options from explicit arguments and an environment lookup
+    pub fn parse(
+        args: impl IntoIterator<Item = String>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut config_path = env("FILLER_CONFIG");
+
+        if let Some(strategy) = env("FILLER_STRATEGY") {
+            options.strategy = strategy;
+        }
+        if let Some(millis) = env("FILLER_MOVE_TIME_MS") {
+            options.move_time =
+                parse_millis(&millis).map_err(|e| format!("FILLER_MOVE_TIME_MS: {}", e))?;
+        }
+
+        options.replay = env("FILLER_REPLAY");
+        options.tui = env("FILLER_TUI");
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--strategy" => {
+                    options.strategy = args.next().ok_or("missing value for --strategy")?;
+                }
+                "--config" => {
+                    config_path = Some(args.next().ok_or("missing value for --config")?);
+                }
+                "--time" => {
+                    let millis = args.next().ok_or("missing value for --time")?;
+                    options.move_time =
+                        parse_millis(&millis).map_err(|e| format!("--time: {}", e))?;
+                }
+                "--replay" => {
+                    options.replay = Some(args.next().ok_or("missing value for --replay")?);
+                }
+                "--tui" => {
+                    options.tui = Some(args.next().ok_or("missing value for --tui")?);
+                }
+                other => return Err(format!("unknown argument '{}'", other)),
+            }
+        }
+
+        if !STRATEGY_NAMES.contains(&options.strategy.as_str()) {
+            return Err(format!(
+                "unknown strategy '{}' (expected one of: {})",
+                options.strategy,
+                STRATEGY_NAMES.join(", ")
+            ));
+        }
+
+        // Weights: defaults, then the config file, then individual environment overrides
+        if let Some(path) = config_path {
+            options.scoring = ScoringConfig::load(&path)?;
+        }
+        options.scoring.apply_env(&env)?;
+
+        Ok(options)
+    }
+}
+
+// Parses a positive number of milliseconds
+fn parse_millis(text: &str) -> Result<Duration, String> {
+    match text.trim().parse::<u64>() {
+        Ok(millis) if millis > 0 => Ok(Duration::from_millis(millis)),
+        _ => Err(format!(
+            "'{}' is not a positive number of milliseconds",
+            text
+        )),
+    }
+}
@@ -0,0 +1,144 @@
+use crate::deadline::Deadline;
+use crate::filler_ai::FillerAi;
+use crate::regions::Reach;
+use crate::replay::Replay;
+
+// What the AI weighed on one turn, drawn over the board by the visualizer
+// Values are scaled to 0..=1 so they map straight onto a colour ramp
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Overlay {
+    pub width: usize,
+    pub height: usize,
+    // Heat of each empty cell relative to the hottest one; None for occupied cells
+    heat: Vec<Option<f64>>,
+    // Best total score among the candidate placements covering each cell, from the
+    // worst candidate (0) to the best (1); None for cells no candidate covers
+    scores: Vec<Option<f64>>,
+    // Board cells of the chosen move
+    pub chosen: Vec<(usize, usize)>,
+    // Empty cells that would split a region both players can still reach
+    pub chokepoints: Vec<(usize, usize)>,
+    pub candidates: usize,
+}
+
+impl Overlay {
+    // Scores every candidate placement of the current piece with the weighted
+    // heuristic, so this is for display only and ignores the move deadline
+    pub fn from_ai(ai: &FillerAi, chosen: Option<(i32, i32)>) -> Self {
+        let (width, height) = (ai.cells.width, ai.cells.height);
+
+        let heat: Vec<Option<i32>> = ai
+            .cells
+            .iter()
+            .map(|(x, y, cell)| cell.is_empty().then(|| ai.heat_map[y][x]))
+            .collect();
+
+        let moves = ai.find_all_valid_placements();
+        let totals = ai.score_placements_until(&moves, Deadline::never());
+        let mut best: Vec<Option<i32>> = vec![None; width * height];
+        for (&(x, y), &total) in moves.iter().zip(&totals) {
+            for (cx, cy) in ai.placed_cells(x, y) {
+                let cell = &mut best[cy as usize * width + cx as usize];
+                *cell = Some(cell.map_or(total, |score| score.max(total)));
+            }
+        }
+
+        let regions = &ai.state.regions;
+        let chokepoints = (0..regions.regions.len())
+            .filter(|&label| regions.regions[label].reach == Reach::Both)
+            .flat_map(|label| regions.chokepoints(&ai.cells, label))
+            .collect();
+
+        Self {
+            width,
+            height,
+            heat: scale_to_max(&heat),
+            scores: scale_to_range(&best),
+            chosen: chosen
+                .map(|(x, y)| {
+                    ai.placed_cells(x, y)
+                        .map(|(cx, cy)| (cx as usize, cy as usize))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            chokepoints,
+            candidates: moves.len(),
+        }
+    }
+
+    pub fn heat(&self, x: usize, y: usize) -> Option<f64> {
+        self.heat.get(y * self.width + x).copied().flatten()
+    }
+
+    pub fn score(&self, x: usize, y: usize) -> Option<f64> {
+        self.scores.get(y * self.width + x).copied().flatten()
+    }
+}
+
+// Rebuilds the AI's view of a recorded game for the replay viewer's overlay
+// Boards are fed in order, as in a live game; stepping forward only feeds the new
+// turns, stepping back starts over from the first one
+pub struct ReplayAnalysis<'a> {
+    replay: &'a Replay,
+    ai: FillerAi,
+    // Turns already fed to `ai`
+    fed: usize,
+}
+
+impl<'a> ReplayAnalysis<'a> {
+    pub fn new(replay: &'a Replay) -> Self {
+        Self {
+            replay,
+            ai: FillerAi::new(replay.player_number),
+            fed: 0,
+        }
+    }
+
+    // Overlay for a turn, with the recorded answer as the chosen move
+    pub fn overlay(&mut self, turn: usize) -> Overlay {
+        // The AI holds turn `fed - 1`; going back means replaying from the start
+        if turn + 1 < self.fed {
+            self.ai = FillerAi::new(self.replay.player_number);
+            self.fed = 0;
+        }
+        for recorded in &self.replay.turns[self.fed..=turn] {
+            let board = recorded.board.clone();
+            let (width, height) = (board.first().map_or(0, Vec::len), board.len());
+            self.ai
+                .update_board(width, height, board)
+                .expect("replay boards only hold board cells");
+            self.ai.update_piece(recorded.piece.clone());
+        }
+        self.fed = turn + 1;
+
+        Overlay::from_ai(&self.ai, self.replay.turns[turn].answer)
+    }
+}
+
+// Divides by the largest value, so 0 stays 0 (heat is 0 where the opponent
+// cannot reach)
+fn scale_to_max(values: &[Option<i32>]) -> Vec<Option<f64>> {
+    let max = values.iter().flatten().copied().max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|value| value.map(|v| v.max(0) as f64 / max as f64))
+        .collect()
+}
+
+// Maps the smallest value to 0 and the largest to 1; a single value maps to 1
+fn scale_to_range(values: &[Option<i32>]) -> Vec<Option<f64>> {
+    let min = values.iter().flatten().copied().min().unwrap_or(0);
+    let max = values.iter().flatten().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|value| {
+            value.map(|v| {
+                if max == min {
+                    1.0
+                } else {
+                    (v as f64 - min as f64) / (max as f64 - min as f64)
+                }
+            })
+        })
+        .collect()
+}
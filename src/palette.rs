@@ -0,0 +1,63 @@
+use crate::board::Cell;
+
+// An RGB colour, shared by the SDL visualizer and the image export
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+// Color scheme for the game
+pub const BACKGROUND: Rgb = Rgb::new(240, 240, 240);
+pub const BORDER_COLOR: Rgb = Rgb::new(64, 64, 64);
+pub const EMPTY_COLOR: Rgb = Rgb::new(200, 200, 200);
+pub const PLAYER1_TERRITORY: Rgb = Rgb::new(255, 100, 100); // Red
+pub const PLAYER1_RECENT: Rgb = Rgb::new(255, 50, 50); // Bright Red
+pub const PLAYER2_TERRITORY: Rgb = Rgb::new(100, 100, 255); // Blue
+pub const PLAYER2_RECENT: Rgb = Rgb::new(50, 50, 255); // Bright Blue
+pub const PLACED_OUTLINE: Rgb = Rgb::new(255, 215, 0); // Gold
+// Overlay ramps: empty cells shade towards HEAT_COLOR as they get hotter, and
+// cells under candidate placements go from SCORE_LOW (worst) to SCORE_HIGH (best)
+pub const HEAT_COLOR: Rgb = Rgb::new(255, 140, 0); // Orange
+pub const SCORE_LOW: Rgb = Rgb::new(70, 40, 90); // Dark Purple
+pub const SCORE_HIGH: Rgb = Rgb::new(60, 220, 90); // Green
+// Outline of the contested chokepoints on the heat layer
+pub const CHOKEPOINT_OUTLINE: Rgb = Rgb::new(20, 20, 20); // Near Black
+
+// Every colour a board is drawn with, outside the overlay ramps
+pub const BOARD_COLORS: [Rgb; 8] = [
+    BACKGROUND,
+    BORDER_COLOR,
+    EMPTY_COLOR,
+    PLAYER1_TERRITORY,
+    PLAYER1_RECENT,
+    PLAYER2_TERRITORY,
+    PLAYER2_RECENT,
+    PLACED_OUTLINE,
+];
+
+// Determine cell color from the owning player number
+// my_player_number keeps player 1 red and player 2 blue whichever side the AI is playing
+pub fn cell_color(cell: Cell, my_player_number: u8) -> Rgb {
+    let mine_is_player1 = my_player_number == 1;
+    match (cell, mine_is_player1) {
+        (Cell::Empty, _) => EMPTY_COLOR,
+        (Cell::Mine, true) | (Cell::Theirs, false) => PLAYER1_TERRITORY,
+        (Cell::MineLast, true) | (Cell::TheirsLast, false) => PLAYER1_RECENT,
+        (Cell::Mine, false) | (Cell::Theirs, true) => PLAYER2_TERRITORY,
+        (Cell::MineLast, false) | (Cell::TheirsLast, true) => PLAYER2_RECENT,
+    }
+}
+
+// Linear mix of two colours, `t` = 0 gives `from` and 1 gives `to`
+pub fn blend(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t.clamp(0.0, 1.0)).round() as u8;
+    Rgb::new(mix(from.r, to.r), mix(from.g, to.g), mix(from.b, to.b))
+}
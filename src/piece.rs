@@ -1,6 +1,9 @@
+use crate::rng::Rng;
+
 // Represents a game piece with dimensions and a 2D character pattern
 // Used to store the shape and layout of pieces in the Filler game
 #[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Piece {
     pub width: usize,
     pub height: usize,
@@ -17,4 +20,133 @@ impl Piece {
             pattern,
         }
     }
+
+    // Returns the (x, y) offsets of every solid cell in the pattern
+    pub fn solid_cells(&self) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for (y, row) in self.pattern.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                if cell != '.' {
+                    cells.push((x, y));
+                }
+            }
+        }
+        cells
+    }
+
+    // Furthest a placement can claim from the owned cell it covers, in either
+    // direction: the larger extent of the solid cells, minus one
+    pub fn reach(&self) -> usize {
+        let cells = self.solid_cells();
+        let span = |coord: fn(&(usize, usize)) -> usize| {
+            let min = cells.iter().map(coord).min().unwrap_or(0);
+            let max = cells.iter().map(coord).max().unwrap_or(0);
+            max - min
+        };
+        span(|&(x, _)| x).max(span(|&(_, y)| y))
+    }
+}
+
+// Settings for random piece generation
+// Sizes bound the bounding box, density is the fraction of that box that is solid
+#[derive(Clone, Debug)]
+pub struct PieceGeneratorConfig {
+    pub min_width: usize,
+    pub max_width: usize,
+    pub min_height: usize,
+    pub max_height: usize,
+    pub density: f64,
+    // Connected pieces grow cell by cell from one seed, like the engine's pieces
+    pub connected: bool,
+}
+
+impl PieceGeneratorConfig {
+    // Mirrors the official engine: piece boxes grow with the board, capped at 7x7
+    pub fn for_board(board_width: usize, board_height: usize) -> Self {
+        let max_side = (board_width.min(board_height) / 5).clamp(2, 7);
+        Self {
+            min_width: 1,
+            max_width: max_side,
+            min_height: 1,
+            max_height: max_side,
+            density: 0.5,
+            connected: true,
+        }
+    }
+}
+
+impl Default for PieceGeneratorConfig {
+    fn default() -> Self {
+        Self::for_board(20, 15)
+    }
+}
+
+// Seeded generator producing engine-like random pieces
+// The same seed and config always produce the same sequence of pieces
+pub struct PieceGenerator {
+    pub config: PieceGeneratorConfig,
+    rng: Rng,
+}
+
+impl PieceGenerator {
+    // Creates a generator from a config and a seed
+    pub fn new(config: PieceGeneratorConfig, seed: u64) -> Self {
+        Self {
+            config,
+            rng: Rng::new(seed),
+        }
+    }
+
+    // Generates the next piece; it always has at least one solid cell
+    pub fn next_piece(&mut self) -> Piece {
+        let config = &self.config;
+        let width = self.rng.range(
+            config.min_width.max(1),
+            config.max_width.max(config.min_width.max(1)),
+        );
+        let height = self.rng.range(
+            config.min_height.max(1),
+            config.max_height.max(config.min_height.max(1)),
+        );
+
+        let area = width * height;
+        let target = ((area as f64 * config.density).round() as usize).clamp(1, area);
+        let mut pattern = vec![vec!['.'; width]; height];
+
+        if config.connected {
+            // Grow from a random seed cell by repeatedly adding a neighbour of a solid cell
+            let mut solid = vec![(self.rng.range(0, width - 1), self.rng.range(0, height - 1))];
+            pattern[solid[0].1][solid[0].0] = 'O';
+
+            while solid.len() < target {
+                let (x, y) = solid[self.rng.range(0, solid.len() - 1)];
+                let (dx, dy) = [(1, 0), (-1, 0), (0, 1), (0, -1)][self.rng.range(0, 3)];
+                let next_x = x as i32 + dx;
+                let next_y = y as i32 + dy;
+
+                if next_x < 0 || next_y < 0 || next_x >= width as i32 || next_y >= height as i32 {
+                    continue;
+                }
+
+                let (next_x, next_y) = (next_x as usize, next_y as usize);
+                if pattern[next_y][next_x] == '.' {
+                    pattern[next_y][next_x] = 'O';
+                    solid.push((next_x, next_y));
+                }
+            }
+        } else {
+            // Scatter cells anywhere in the box
+            let mut placed = 0;
+            while placed < target {
+                let x = self.rng.range(0, width - 1);
+                let y = self.rng.range(0, height - 1);
+                if pattern[y][x] == '.' {
+                    pattern[y][x] = 'O';
+                    placed += 1;
+                }
+            }
+        }
+
+        Piece::new(width, height, pattern)
+    }
 }
@@ -0,0 +1,238 @@
+use std::fmt;
+use std::io::{BufRead, Lines};
+
+use crate::piece::Piece;
+
+// Cell characters the engine may send on the board
+const BOARD_CELLS: &[char] = &['.', '@', 'a', '$', 's'];
+// Cell characters the engine may send in a piece
+const PIECE_CELLS: &[char] = &['.', 'O'];
+
+// Error raised when engine input does not match the expected protocol
+// Carries the 1-based input line number, what the parser expected and the text it found
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolError {
+    pub line: usize,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl ProtocolError {
+    fn new(line: usize, expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self {
+            line,
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {}, found {:?}",
+            self.line, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+// One turn of engine input: the board followed by the piece to place
+#[derive(Debug, Clone, PartialEq)]
+pub struct Turn {
+    pub width: usize,
+    pub height: usize,
+    pub board: Vec<Vec<char>>,
+    pub piece: Piece,
+}
+
+// Reads the engine protocol line by line, tracking line numbers for diagnostics
+pub struct ProtocolReader<R: BufRead> {
+    lines: Lines<R>,
+    line_number: usize,
+}
+
+impl<R: BufRead> ProtocolReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_number: 0,
+        }
+    }
+
+    // 1-based number of the last line read
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    // Reads the "$$$ exec pN : [path]" greeting and returns N
+    pub fn read_player_number(&mut self) -> Result<u8, ProtocolError> {
+        let expected = "player line \"$$$ exec p1\" or \"$$$ exec p2\"";
+        let line = self.next_line(expected)?;
+
+        match line
+            .strip_prefix("$$$ exec p")
+            .and_then(|rest| rest.chars().next())
+        {
+            Some('1') => Ok(1),
+            Some('2') => Ok(2),
+            _ => Err(ProtocolError::new(self.line_number, expected, line)),
+        }
+    }
+
+    // Reads the next board and piece
+    // Returns Ok(None) when the input ends cleanly between turns
+    pub fn read_turn(&mut self) -> Result<Option<Turn>, ProtocolError> {
+        let Some(header) = self.try_next_line()? else {
+            return Ok(None);
+        };
+        let (width, height) = parse_board_header(&header)
+            .map_err(|expected| ProtocolError::new(self.line_number, expected, header.as_str()))?;
+
+        // Column numbers: indentation followed by one digit per column
+        let expected = format!("column index line with {} digits", width);
+        let columns = self.next_line(&expected)?;
+        let digits = columns.trim_start();
+        if digits.len() != width || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ProtocolError::new(self.line_number, expected, columns));
+        }
+
+        let mut board = Vec::with_capacity(height);
+        for row_index in 0..height {
+            let expected = format!("board row {:03} of width {}", row_index, width);
+            let row = self.next_line(&expected)?;
+            board.push(self.parse_board_row(&row, row_index, width, &expected)?);
+        }
+
+        let header = self.next_line("piece header \"Piece <width> <height>:\"")?;
+        let (piece_width, piece_height) = parse_piece_header(&header)
+            .map_err(|expected| ProtocolError::new(self.line_number, expected, header.as_str()))?;
+
+        let mut pattern = Vec::with_capacity(piece_height);
+        for _ in 0..piece_height {
+            let expected = format!("piece row of width {} using '.' and 'O'", piece_width);
+            let row = self.next_line(&expected)?;
+            let cells: Vec<char> = row.chars().collect();
+            if cells.len() != piece_width || !cells.iter().all(|c| PIECE_CELLS.contains(c)) {
+                return Err(ProtocolError::new(self.line_number, expected, row));
+            }
+            pattern.push(cells);
+        }
+
+        Ok(Some(Turn {
+            width,
+            height,
+            board,
+            piece: Piece::new(piece_width, piece_height, pattern),
+        }))
+    }
+
+    // Splits "NNN <cells>" and checks the row number, width and cell characters
+    fn parse_board_row(
+        &self,
+        row: &str,
+        row_index: usize,
+        width: usize,
+        expected: &str,
+    ) -> Result<Vec<char>, ProtocolError> {
+        let error = || ProtocolError::new(self.line_number, expected, row);
+
+        let (number, data) = row.split_once(' ').ok_or_else(error)?;
+        if number.parse::<usize>() != Ok(row_index) {
+            return Err(error());
+        }
+
+        let cells: Vec<char> = data.chars().collect();
+        if cells.len() != width || !cells.iter().all(|c| BOARD_CELLS.contains(c)) {
+            return Err(error());
+        }
+        Ok(cells)
+    }
+
+    // Reads a line, treating end of input as an error
+    // `expected` describes the line for the error message
+    pub fn next_line(&mut self, expected: &str) -> Result<String, ProtocolError> {
+        match self.try_next_line()? {
+            Some(line) => Ok(line),
+            None => Err(ProtocolError::new(
+                self.line_number + 1,
+                expected,
+                "end of input",
+            )),
+        }
+    }
+
+    // Reads a line, returning None at end of input
+    fn try_next_line(&mut self) -> Result<Option<String>, ProtocolError> {
+        match self.lines.next() {
+            None => Ok(None),
+            Some(Ok(line)) => {
+                self.line_number += 1;
+                Ok(Some(line.trim_end_matches('\r').to_string()))
+            }
+            Some(Err(e)) => Err(ProtocolError::new(
+                self.line_number + 1,
+                "readable input",
+                e.to_string(),
+            )),
+        }
+    }
+}
+
+// Parses board header lines such as "Anfield 20 15:"
+// Returns width and height, or a description of the expected text
+pub fn parse_board_header(line: &str) -> Result<(usize, usize), &'static str> {
+    parse_header(line, "Anfield ").ok_or("board header \"Anfield <width> <height>:\"")
+}
+
+// Parses piece header lines such as "Piece 4 1:"
+// Returns width and height, or a description of the expected text
+pub fn parse_piece_header(line: &str) -> Result<(usize, usize), &'static str> {
+    parse_header(line, "Piece ").ok_or("piece header \"Piece <width> <height>:\"")
+}
+
+// Shared "<keyword> <width> <height>:" parser
+fn parse_header(line: &str, keyword: &str) -> Option<(usize, usize)> {
+    let dims = line.strip_prefix(keyword)?.strip_suffix(':')?;
+    let parts: Vec<&str> = dims.split_whitespace().collect();
+    match parts.as_slice() {
+        [width, height] => Some((width.parse().ok()?, height.parse().ok()?)),
+        _ => None,
+    }
+}
+
+// Formats the board and piece exactly as the engine sends them each turn
+pub fn format_turn(board: &[Vec<char>], piece: &Piece) -> String {
+    let width = board.first().map_or(0, Vec::len);
+    let mut text = format!("Anfield {} {}:\n    ", width, board.len());
+    for x in 0..width {
+        text.push(char::from(b'0' + (x % 10) as u8));
+    }
+    text.push('\n');
+
+    for (y, row) in board.iter().enumerate() {
+        text.push_str(&format!("{:03} ", y));
+        text.extend(row.iter());
+        text.push('\n');
+    }
+
+    text.push_str(&format!("Piece {} {}:\n", piece.width, piece.height));
+    for row in &piece.pattern {
+        text.extend(row.iter());
+        text.push('\n');
+    }
+
+    text
+}
+
+// Parses a player's "x y" reply
+// Returns None for anything that is not two integers
+pub fn parse_move(line: &str) -> Option<(i32, i32)> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        [x, y] => Some((x.parse().ok()?, y.parse().ok()?)),
+        _ => None,
+    }
+}
@@ -0,0 +1,120 @@
+use crate::board::{Board, Side};
+use crate::filler_ai::FillerAi;
+use crate::map::Map;
+use crate::piece::{Piece, PieceGenerator, PieceGeneratorConfig};
+use crate::player::Player;
+use crate::protocol::format_turn;
+use crate::rules::{apply_placement, count_territory, is_valid_placement};
+use crate::strategy::Strategy;
+
+// Local referee that runs the Filler rules for two players
+// Holds the board, tracks which players can still move, and formats the engine protocol
+pub struct Referee {
+    pub board_width: usize,
+    pub board_height: usize,
+    // Seen from player 1: Mine is player 1, Theirs is player 2
+    pub board: Board,
+    pub players: [Player; 2],
+    // A player drops out after its first invalid or missing move
+    pub active: [bool; 2],
+    piece_generator: PieceGenerator,
+}
+
+impl Referee {
+    // Creates a referee for a fresh game on the given map
+    // The seed drives the piece generator so a match can be replayed exactly
+    pub fn new(map: &Map, seed: u64) -> Self {
+        Self {
+            board_width: map.width,
+            board_height: map.height,
+            board: map.to_cells(),
+            players: [Player::new(1), Player::new(2)],
+            active: [true, true],
+            piece_generator: PieceGenerator::new(
+                PieceGeneratorConfig::for_board(map.width, map.height),
+                seed,
+            ),
+        }
+    }
+
+    // Deals the next random piece
+    pub fn next_piece(&mut self) -> Piece {
+        self.piece_generator.next_piece()
+    }
+
+    // The board in engine symbols, as both players receive it
+    pub fn grid(&self) -> Vec<Vec<char>> {
+        self.board.to_chars(&self.players[0], &self.players[1])
+    }
+
+    // Formats the board and piece exactly as the engine sends them each turn
+    pub fn format_turn(&self, piece: &Piece) -> String {
+        format_turn(&self.grid(), piece)
+    }
+
+    // Applies a player's reply if it is legal, otherwise drops the player from the game
+    // Returns whether the move was accepted
+    pub fn play_move(
+        &mut self,
+        player_index: usize,
+        piece: &Piece,
+        reply: Option<(i32, i32)>,
+    ) -> bool {
+        let side = if player_index == 0 {
+            Side::Me
+        } else {
+            Side::Opponent
+        };
+
+        match reply {
+            Some((x, y)) if is_valid_placement(&self.board, piece, x, y, side) => {
+                apply_placement(&mut self.board, piece, x, y, side);
+                true
+            }
+            _ => {
+                self.active[player_index] = false;
+                false
+            }
+        }
+    }
+
+    // Plays the rest of the game in-process, without spawning player executables
+    // ais[i] and strategies[i] play as player i + 1; returns the final scores
+    pub fn play_in_process(
+        &mut self,
+        ais: &mut [FillerAi; 2],
+        strategies: [&mut dyn Strategy; 2],
+    ) -> (usize, usize) {
+        while !self.is_over() {
+            for index in 0..2 {
+                if !self.active[index] {
+                    continue;
+                }
+
+                let piece = self.next_piece();
+                let ai = &mut ais[index];
+                ai.update_board(self.board_width, self.board_height, self.grid())
+                    .expect("the referee only writes board cells");
+                ai.update_piece(piece.clone());
+
+                let reply = strategies[index].choose_move(ai);
+                self.play_move(index, &piece, reply);
+            }
+        }
+
+        self.scores()
+    }
+
+    // The game is over once neither player can move
+    pub fn is_over(&self) -> bool {
+        !self.active[0] && !self.active[1]
+    }
+
+    // Returns the territory size of player 1 and player 2
+    pub fn scores(&self) -> (usize, usize) {
+        (
+            count_territory(&self.board, Side::Me),
+            count_territory(&self.board, Side::Opponent),
+        )
+    }
+}
@@ -0,0 +1,246 @@
+use crate::board::Board;
+use std::collections::BTreeSet;
+
+// Unvisited marker for the chokepoint search
+const UNSEEN: usize = usize::MAX;
+
+// Which players border an empty region
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reach {
+    Mine,
+    Theirs,
+    Both,
+    Neither,
+}
+
+impl Reach {
+    fn from_contact(touches_mine: bool, touches_theirs: bool) -> Self {
+        match (touches_mine, touches_theirs) {
+            (true, true) => Reach::Both,
+            (true, false) => Reach::Mine,
+            (false, true) => Reach::Theirs,
+            (false, false) => Reach::Neither,
+        }
+    }
+
+    pub fn includes_me(self) -> bool {
+        matches!(self, Reach::Mine | Reach::Both)
+    }
+
+    pub fn includes_them(self) -> bool {
+        matches!(self, Reach::Theirs | Reach::Both)
+    }
+}
+
+// One connected area of empty cells
+#[derive(Clone, Debug, PartialEq)]
+pub struct Region {
+    pub size: usize,
+    pub reach: Reach,
+}
+
+// Connected empty regions of a board and who can reach them
+// Regions are 8-connected and a region is reachable by a player whose territory
+// touches it (diagonals included), since a piece may step diagonally off an owned cell
+// A region that gets split hands its cells to fresh labels and keeps size 0
+#[derive(Clone, Debug, Default)]
+pub struct RegionAnalysis {
+    pub width: usize,
+    pub height: usize,
+    // Regions by label, in the row-major order of their first cell when computed
+    pub regions: Vec<Region>,
+    labels: Vec<Option<usize>>,
+    // Empty cells among each cell's eight neighbours
+    open_neighbours: Vec<u8>,
+}
+
+impl RegionAnalysis {
+    // Labels every empty area of the board
+    pub fn compute(board: &Board) -> Self {
+        let mut analysis = Self {
+            width: board.width,
+            height: board.height,
+            regions: Vec::new(),
+            labels: vec![None; board.width * board.height],
+            open_neighbours: vec![0; board.width * board.height],
+        };
+
+        for (x, y, _) in board.iter() {
+            analysis.open_neighbours[y * board.width + x] = board
+                .neighbours(x as i32, y as i32)
+                .filter(|(_, _, cell)| cell.is_empty())
+                .count() as u8;
+        }
+        for (x, y, cell) in board.iter() {
+            if cell.is_empty() && analysis.labels[y * board.width + x].is_none() {
+                analysis.flood(board, x, y, None);
+            }
+        }
+
+        analysis
+    }
+
+    // Region label of an empty cell, None for occupied or off-board cells
+    pub fn label(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.labels[y * self.width + x]
+    }
+
+    // Number of regions that still hold at least one empty cell
+    pub fn region_count(&self) -> usize {
+        self.regions.iter().filter(|region| region.size > 0).count()
+    }
+
+    // Number of empty cells around (x, y), diagonals included
+    pub fn open_neighbours(&self, x: usize, y: usize) -> usize {
+        self.open_neighbours[y * self.width + x] as usize
+    }
+
+    // Total empty cells in regions with the given reach
+    pub fn cells_with_reach(&self, reach: Reach) -> usize {
+        self.regions
+            .iter()
+            .filter(|region| region.reach == reach)
+            .map(|region| region.size)
+            .sum()
+    }
+
+    // Removes newly filled cells and splits the regions they cut apart
+    // Only the regions that lost cells are flooded again, which also refreshes their
+    // reach: a cell can only change owner by being filled, and a filled cell
+    // belonged to every region it touches
+    pub fn fill(&mut self, board: &Board, filled: &[(usize, usize)]) {
+        let mut touched = BTreeSet::new();
+        for &(x, y) in filled {
+            if let Some(label) = self.labels[y * self.width + x].take() {
+                touched.insert(label);
+                for (nx, ny, _) in board.neighbours(x as i32, y as i32) {
+                    self.open_neighbours[ny as usize * self.width + nx as usize] -= 1;
+                }
+            }
+        }
+
+        for label in touched {
+            self.regions[label].size = 0;
+            // Every piece left of the region borders a filled cell
+            for &(x, y) in filled {
+                for (nx, ny, _) in board.neighbours(x as i32, y as i32) {
+                    let (nx, ny) = (nx as usize, ny as usize);
+                    if self.labels[ny * self.width + nx] == Some(label) {
+                        self.flood(board, nx, ny, Some(label));
+                    }
+                }
+            }
+        }
+    }
+
+    // Cells whose filling would split a region in two or more, row-major
+    // Found with a depth-first search over the region, so this is only run on demand
+    pub fn chokepoints(&self, board: &Board, label: usize) -> Vec<(usize, usize)> {
+        match self.labels.iter().position(|&cell| cell == Some(label)) {
+            Some(root) => self.find_chokepoints(board, root),
+            None => Vec::new(),
+        }
+    }
+
+    // Gives the empty cells labelled `from` that connect to (x, y) a fresh label,
+    // counting them and the players they touch
+    fn flood(&mut self, board: &Board, x: usize, y: usize, from: Option<usize>) {
+        let label = self.regions.len();
+        let (mut size, mut touches_mine, mut touches_theirs) = (0, false, false);
+        let mut stack = vec![(x as i32, y as i32)];
+        self.labels[y * self.width + x] = Some(label);
+
+        while let Some((cx, cy)) = stack.pop() {
+            size += 1;
+            for (nx, ny, cell) in board.neighbours(cx, cy) {
+                let index = ny as usize * self.width + nx as usize;
+                if cell.is_empty() {
+                    if self.labels[index] == from {
+                        self.labels[index] = Some(label);
+                        stack.push((nx, ny));
+                    }
+                } else if cell.is_mine() {
+                    touches_mine = true;
+                } else {
+                    touches_theirs = true;
+                }
+            }
+        }
+
+        self.regions.push(Region {
+            size,
+            reach: Reach::from_contact(touches_mine, touches_theirs),
+        });
+    }
+
+    // Articulation points of the region's cell graph, from an iterative
+    // Tarjan depth-first search rooted at `root`
+    fn find_chokepoints(&self, board: &Board, root: usize) -> Vec<(usize, usize)> {
+        // Search order of each cell
+        let mut order = vec![UNSEEN; self.width * self.height];
+        // Indexed by search order: lowest order reachable through one back edge,
+        // and the parent's order
+        let mut low = vec![0];
+        let mut parent = vec![UNSEEN];
+        let mut cut = Vec::new();
+        let mut root_children = 0;
+
+        // (cell, its search order, neighbours still to visit)
+        let mut stack = vec![(root, 0, self.empty_neighbours(board, root))];
+        order[root] = 0;
+
+        while let Some((cell, cell_order, pending)) = stack.last_mut() {
+            let (cell, cell_order) = (*cell, *cell_order);
+            match pending.pop() {
+                Some(next) if order[next] == UNSEEN => {
+                    let next_order = low.len();
+                    order[next] = next_order;
+                    low.push(next_order);
+                    parent.push(cell_order);
+                    if cell == root {
+                        root_children += 1;
+                    }
+                    stack.push((next, next_order, self.empty_neighbours(board, next)));
+                }
+                Some(next) => {
+                    if order[next] != parent[cell_order] {
+                        low[cell_order] = low[cell_order].min(order[next]);
+                    }
+                }
+                None => {
+                    stack.pop();
+                    let up = parent[cell_order];
+                    if up == UNSEEN {
+                        continue;
+                    }
+                    low[up] = low[up].min(low[cell_order]);
+                    // The root is handled below: it splits the region only with two subtrees
+                    if up != 0 && low[cell_order] >= up {
+                        cut.push(stack.last().map_or(root, |&(above, _, _)| above));
+                    }
+                }
+            }
+        }
+        if root_children > 1 {
+            cut.push(root);
+        }
+
+        cut.sort_unstable();
+        cut.dedup();
+        cut.into_iter()
+            .map(|index| (index % self.width, index / self.width))
+            .collect()
+    }
+
+    fn empty_neighbours(&self, board: &Board, index: usize) -> Vec<usize> {
+        let (x, y) = ((index % self.width) as i32, (index / self.width) as i32);
+        board
+            .neighbours(x, y)
+            .filter(|(_, _, cell)| cell.is_empty())
+            .map(|(nx, ny, _)| ny as usize * self.width + nx as usize)
+            .collect()
+    }
+}
@@ -0,0 +1,211 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::board::{Board, Cell};
+use crate::piece::Piece;
+use crate::player::Player;
+use crate::protocol::{ProtocolError, ProtocolReader, format_turn};
+
+// One turn as the AI received it, with the move it answered and how long that took
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayTurn {
+    pub board: Vec<Vec<char>>,
+    pub piece: Piece,
+    // None when no placement was legal and "0 0" was sent
+    pub answer: Option<(i32, i32)>,
+    // From the moment the turn was read to the moment the answer was ready
+    pub elapsed: Duration,
+}
+
+impl ReplayTurn {
+    // Board cells the answered piece covered, row-major; empty without an answer
+    pub fn placed_cells(&self) -> Vec<(usize, usize)> {
+        let Some((x, y)) = self.answer else {
+            return Vec::new();
+        };
+        let height = self.board.len() as i32;
+        let width = self.board.first().map_or(0, Vec::len) as i32;
+        self.piece
+            .solid_cells()
+            .into_iter()
+            .map(|(px, py)| (x + px as i32, y + py as i32))
+            .filter(|&(cx, cy)| cx >= 0 && cx < width && cy >= 0 && cy < height)
+            .map(|(cx, cy)| (cx as usize, cy as usize))
+            .collect()
+    }
+}
+
+// A recorded game from one player's side
+// The file reads like the engine's own input: the player line, then every turn in
+// the engine format followed by a "move <x> <y> <micros>us" line ("move none ..."
+// when the AI had no legal placement)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    pub player_number: u8,
+    pub turns: Vec<ReplayTurn>,
+}
+
+impl Replay {
+    pub fn read<R: BufRead>(input: R) -> Result<Self, ProtocolError> {
+        let mut reader = ProtocolReader::new(input);
+        let mut replay = Self {
+            player_number: reader.read_player_number()?,
+            turns: Vec::new(),
+        };
+
+        while let Some(turn) = reader.read_turn()? {
+            let expected = "move line \"move <x> <y> <micros>us\" or \"move none <micros>us\"";
+            let line = reader.next_line(expected)?;
+            let (answer, elapsed) = parse_move_line(&line).ok_or_else(|| ProtocolError {
+                line: reader.line_number(),
+                expected: expected.to_string(),
+                actual: line.clone(),
+            })?;
+            replay.turns.push(ReplayTurn {
+                board: turn.board,
+                piece: turn.piece,
+                answer,
+                elapsed,
+            });
+        }
+
+        Ok(replay)
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+        Self::read(BufReader::new(file)).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    // Board of a turn seen from the recorded player's side
+    // Panics on a symbol that is not a board cell, which a replay read from a file
+    // never has: the protocol reader rejects those
+    pub fn cells(&self, turn: usize) -> Board {
+        let opponent_number = if self.player_number == 1 { 2 } else { 1 };
+        Board::from_chars(
+            &self.turns[turn].board,
+            &Player::new(self.player_number),
+            &Player::new(opponent_number),
+        )
+        .unwrap_or_else(|e| panic!("turn {} of the replay: {}", turn + 1, e))
+    }
+
+    // Last board with our last answer placed on it, which the file never shows
+    pub fn final_cells(&self) -> Option<Board> {
+        let last = self.turns.len().checked_sub(1)?;
+        let mut board = self.cells(last);
+        for (x, y) in self.turns[last].placed_cells() {
+            if board.cell(x, y).is_empty() {
+                board.set(x, y, Cell::MineLast);
+            }
+        }
+        Some(board)
+    }
+}
+
+// Position of a viewer stepping through a replay
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplayCursor {
+    pub turn: usize,
+    turns: usize,
+}
+
+impl ReplayCursor {
+    pub fn new(turns: usize) -> Self {
+        Self { turn: 0, turns }
+    }
+
+    // Moves `delta` turns forward (backward when negative), stopping at either end
+    // Returns whether the turn changed
+    pub fn step(&mut self, delta: isize) -> bool {
+        let last = self.turns.saturating_sub(1);
+        self.jump(self.turn.saturating_add_signed(delta).min(last))
+    }
+
+    pub fn first(&mut self) -> bool {
+        self.jump(0)
+    }
+
+    pub fn last(&mut self) -> bool {
+        self.jump(self.turns.saturating_sub(1))
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.turn + 1 >= self.turns
+    }
+
+    fn jump(&mut self, turn: usize) -> bool {
+        let changed = turn != self.turn;
+        self.turn = turn;
+        changed
+    }
+}
+
+// Appends turns to a replay as the game goes
+// Each turn is flushed once answered, so a game the engine kills still leaves
+// every completed turn on disk
+pub struct ReplayWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> ReplayWriter<W> {
+    // Starts a replay with the player line
+    pub fn new(mut out: W, player_number: u8) -> io::Result<Self> {
+        writeln!(out, "$$$ exec p{} : [replay]", player_number)?;
+        out.flush()?;
+        Ok(Self { out })
+    }
+
+    pub fn record(
+        &mut self,
+        board: &[Vec<char>],
+        piece: &Piece,
+        answer: Option<(i32, i32)>,
+        elapsed: Duration,
+    ) -> io::Result<()> {
+        self.out.write_all(format_turn(board, piece).as_bytes())?;
+        match answer {
+            Some((x, y)) => writeln!(self.out, "move {} {} {}us", x, y, elapsed.as_micros())?,
+            None => writeln!(self.out, "move none {}us", elapsed.as_micros())?,
+        }
+        self.out.flush()
+    }
+}
+
+impl ReplayWriter<BufWriter<File>> {
+    // Creates the replay file at `path`
+    // When `path` is a directory, each game gets its own file inside it, named
+    // after the start time, the process and the player number
+    pub fn create(path: &str, player_number: u8) -> io::Result<Self> {
+        let path = Path::new(path);
+        let file_path: PathBuf = if path.is_dir() {
+            let started = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            path.join(format!(
+                "filler-{}-{}-p{}.replay",
+                started,
+                std::process::id(),
+                player_number
+            ))
+        } else {
+            path.to_path_buf()
+        };
+        Self::new(BufWriter::new(File::create(file_path)?), player_number)
+    }
+}
+
+// Parses "move <x> <y> <micros>us" or "move none <micros>us"
+fn parse_move_line(line: &str) -> Option<(Option<(i32, i32)>, Duration)> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let (answer, micros) = match parts.as_slice() {
+        ["move", "none", micros] => (None, micros),
+        ["move", x, y, micros] => (Some((x.parse().ok()?, y.parse().ok()?)), micros),
+        _ => return None,
+    };
+    let micros = micros.strip_suffix("us")?.parse().ok()?;
+    Some((answer, Duration::from_micros(micros)))
+}
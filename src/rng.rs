@@ -0,0 +1,41 @@
+// Small seedable pseudo-random number generator (xorshift64*)
+// Deterministic for a given seed so local matches and tests can be replayed exactly
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    // Creates a generator from a seed; any seed (including 0) is valid
+    pub fn new(seed: u64) -> Self {
+        // Scramble the seed with splitmix64 so nearby seeds give unrelated streams
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Self {
+            state: if z == 0 { 0x2545_F491_4F6C_DD1D } else { z },
+        }
+    }
+
+    // Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Returns a value in the inclusive range [low, high]
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        if high <= low {
+            return low;
+        }
+        low + (self.next_u64() % (high - low + 1) as u64) as usize
+    }
+
+    // Returns a float in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
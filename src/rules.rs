@@ -0,0 +1,227 @@
+use crate::bitboard::{Bitboard, CompiledPiece};
+use crate::board::{Board, Cell, Side};
+use crate::piece::Piece;
+
+// Placement rules shared by the AI and the local referee
+// A placement is legal when every solid piece cell lands on the board, none of them
+// covers an opponent cell, and exactly one of them covers the player's own territory
+
+// Checks whether the piece can be placed at the (possibly negative) offset for the given side
+pub fn is_valid_placement(board: &Board, piece: &Piece, x: i32, y: i32, side: Side) -> bool {
+    let mut cell_overlap_count = 0;
+
+    // Check each solid cell in the piece
+    for (piece_x, piece_y) in piece.solid_cells() {
+        // Off the board is never legal
+        let Some(cell) = board.get(x + piece_x as i32, y + piece_y as i32) else {
+            return false;
+        };
+
+        // Check opponent collision
+        if side.other().owns(cell) {
+            return false;
+        }
+
+        // Count overlaps with own territory
+        if side.owns(cell) {
+            cell_overlap_count += 1;
+            if cell_overlap_count > 1 {
+                return false;
+            }
+        }
+    }
+
+    // Valid placement if exactly one overlap
+    cell_overlap_count == 1
+}
+
+// Lists every legal offset for the piece in row-major order
+// Offsets start at (1 - piece width, 1 - piece height) so leading empty rows/columns can hang off the edge
+// Boards up to MAX_BITBOARD_WIDTH columns use the bitboard; wider ones fall back to the cell scan
+pub fn valid_placements(board: &Board, piece: &Piece, side: Side) -> Vec<(i32, i32)> {
+    valid_placements_with(
+        board,
+        Bitboard::from_board(board).as_ref(),
+        piece,
+        CompiledPiece::new(piece).as_ref(),
+        side,
+    )
+}
+
+// valid_placements with the bitboard and compiled piece built ahead of time, for
+// callers that list moves on the same board or for the same piece more than once
+// Either one missing (too wide) falls back to the cell scan
+pub fn valid_placements_with(
+    board: &Board,
+    bitboard: Option<&Bitboard>,
+    piece: &Piece,
+    compiled: Option<&CompiledPiece>,
+    side: Side,
+) -> Vec<(i32, i32)> {
+    match (bitboard, compiled) {
+        (Some(bitboard), Some(compiled)) => bitboard.valid_placements(compiled, side),
+        _ => scan_valid_placements(board, piece, side),
+    }
+}
+
+// A board with its bitboard kept in step, for the searches and playouts that list
+// moves and place pieces over and over on copies of the same board
+#[derive(Clone, Debug, PartialEq)]
+pub struct Position {
+    pub board: Board,
+    // None when the board is wider than a bitboard
+    pub bitboard: Option<Bitboard>,
+}
+
+impl Position {
+    pub fn new(board: Board) -> Self {
+        let bitboard = Bitboard::from_board(&board);
+        Self { board, bitboard }
+    }
+
+    pub fn valid_placements(
+        &self,
+        piece: &Piece,
+        compiled: Option<&CompiledPiece>,
+        side: Side,
+    ) -> Vec<(i32, i32)> {
+        valid_placements_with(&self.board, self.bitboard.as_ref(), piece, compiled, side)
+    }
+
+    // Applies a placement to the board and patches the bitboard to match
+    pub fn place(
+        &mut self,
+        piece: &Piece,
+        compiled: Option<&CompiledPiece>,
+        x: i32,
+        y: i32,
+        side: Side,
+    ) {
+        apply_placement(&mut self.board, piece, x, y, side);
+        match (&mut self.bitboard, compiled) {
+            (Some(bitboard), Some(compiled)) => bitboard.place(compiled, x, y, side),
+            (Some(_), None) => self.bitboard = Bitboard::from_board(&self.board),
+            (None, _) => {}
+        }
+    }
+}
+
+// Lists the legal offsets that cover one of the anchor cells with a solid piece cell
+// A legal placement overlaps exactly one owned cell, so when the anchors include
+// every owned cell that can be that overlap, the result equals valid_placements
+// (same moves, same row-major order)
+pub fn anchored_placements(
+    board: &Board,
+    piece: &Piece,
+    anchors: impl IntoIterator<Item = (usize, usize)>,
+) -> Vec<(i32, i32)> {
+    anchored_placements_with(board, None, piece, None, anchors)
+}
+
+// anchored_placements with the bitboard and compiled piece built ahead of time, so
+// each candidate is checked with a few mask operations instead of a cell loop
+// Either one missing (too wide) falls back to checking the cells
+pub fn anchored_placements_with(
+    board: &Board,
+    bitboard: Option<&Bitboard>,
+    piece: &Piece,
+    compiled: Option<&CompiledPiece>,
+    anchors: impl IntoIterator<Item = (usize, usize)>,
+) -> Vec<(i32, i32)> {
+    let solid_cells = piece.solid_cells();
+    // Steps from each solid cell to the solid cells around it; those cells must be
+    // empty on the board when this piece cell is the one on the anchor
+    let steps: Vec<Vec<(i32, i32)>> = solid_cells
+        .iter()
+        .map(|&(x, y)| {
+            solid_cells
+                .iter()
+                .filter(|&&(ox, oy)| {
+                    (ox, oy) != (x, y) && ox.abs_diff(x) <= 1 && oy.abs_diff(y) <= 1
+                })
+                .map(|&(ox, oy)| (ox as i32 - x as i32, oy as i32 - y as i32))
+                .collect()
+        })
+        .collect();
+
+    let mut offsets = Vec::new();
+    for (anchor_x, anchor_y) in anchors {
+        let (anchor_x, anchor_y) = (anchor_x as i32, anchor_y as i32);
+        for (&(piece_x, piece_y), cell_steps) in solid_cells.iter().zip(&steps) {
+            let fits = cell_steps.iter().all(|&(dx, dy)| {
+                board
+                    .get(anchor_x + dx, anchor_y + dy)
+                    .is_some_and(Cell::is_empty)
+            });
+            if fits {
+                offsets.push((anchor_x - piece_x as i32, anchor_y - piece_y as i32));
+            }
+        }
+    }
+    offsets.sort_unstable_by_key(|&(x, y)| (y, x));
+    offsets.dedup();
+
+    if let (Some(bitboard), Some(compiled)) = (bitboard, compiled) {
+        offsets.retain(|&(x, y)| bitboard.overlap_count(compiled, x, y, Side::Me) == Some(1));
+        return offsets;
+    }
+    offsets.retain(|&(x, y)| {
+        let mut overlaps = 0;
+        for &(piece_x, piece_y) in &solid_cells {
+            match board.get(x + piece_x as i32, y + piece_y as i32) {
+                Some(cell) if cell.is_mine() => overlaps += 1,
+                Some(cell) if cell.is_empty() => {}
+                _ => return false,
+            }
+        }
+        overlaps == 1
+    });
+    offsets
+}
+
+// Whether every solid cell of the piece touches another solid cell, diagonals included
+// Then the owned cell under a legal placement always borders an empty cell (the
+// piece cell next to it), so owned cells with an empty neighbour are enough anchors
+pub fn has_no_isolated_cells(piece: &Piece) -> bool {
+    let solid_cells = piece.solid_cells();
+    solid_cells.len() > 1
+        && solid_cells.iter().all(|&(x, y)| {
+            solid_cells
+                .iter()
+                .any(|&(ox, oy)| (ox, oy) != (x, y) && ox.abs_diff(x) <= 1 && oy.abs_diff(y) <= 1)
+        })
+}
+
+// Cell-by-cell version of valid_placements that works for any board size
+pub fn scan_valid_placements(board: &Board, piece: &Piece, side: Side) -> Vec<(i32, i32)> {
+    let mut valid_moves = Vec::new();
+
+    for y in (1 - piece.height as i32)..board.height as i32 {
+        for x in (1 - piece.width as i32)..board.width as i32 {
+            if is_valid_placement(board, piece, x, y, side) {
+                valid_moves.push((x, y));
+            }
+        }
+    }
+
+    valid_moves
+}
+
+// Writes a placement onto the board for the given side
+// The side's previous last-placed cells become plain territory, the new cells are its last placed
+pub fn apply_placement(board: &mut Board, piece: &Piece, x: i32, y: i32, side: Side) {
+    board.replace(side.last_placed(), side.territory());
+
+    for (piece_x, piece_y) in piece.solid_cells() {
+        let board_x = x + piece_x as i32;
+        let board_y = y + piece_y as i32;
+        if board.in_bounds(board_x, board_y) {
+            board.set(board_x as usize, board_y as usize, side.last_placed());
+        }
+    }
+}
+
+// Counts the cells owned by a side, including its last placed piece
+pub fn count_territory(board: &Board, side: Side) -> usize {
+    board.iter().filter(|&(_, _, cell)| side.owns(cell)).count()
+}
@@ -0,0 +1,318 @@
+use std::cmp::Reverse;
+use std::time::Duration;
+
+use crate::bitboard::CompiledPiece;
+use crate::board::{Board, Side};
+use crate::deadline::Deadline;
+use crate::distance::DistanceField;
+use crate::filler_ai::FillerAi;
+use crate::piece::Piece;
+use crate::rules::Position;
+use crate::strategy::Strategy;
+
+// Depth-limited alpha-beta lookahead over our placement and the opponent's replies
+// Our first ply uses the real piece. The engine does not reveal upcoming pieces, so
+// our later plies take turns through a set of likely pieces and the opponent's plies
+// try every one of them and keep the worst for us. Iterative deepening keeps the
+// best move from the deepest completed search when the time budget runs out.
+pub struct AlphaBetaSearch {
+    // Plies to look ahead: 1 = greedy, 2 = our move + opponent reply, ...
+    pub max_depth: usize,
+    // Cap on the lookahead time; the AI's move deadline bounds the whole move
+    pub time_budget: Duration,
+    // Only the most promising moves are expanded at each node
+    pub max_branching: usize,
+    pub likely_pieces: Vec<Piece>,
+}
+
+impl Default for AlphaBetaSearch {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            time_budget: Duration::from_millis(500),
+            max_branching: 8,
+            likely_pieces: default_likely_pieces(),
+        }
+    }
+}
+
+// Sorts moves by the AI's heuristic score, best first, scoring until the deadline
+// Unscored moves keep their order after the scored ones
+pub fn order_by_score(
+    ai: &FillerAi,
+    moves: Vec<(i32, i32)>,
+    deadline: Deadline,
+) -> Vec<(i32, i32)> {
+    let scores = ai.score_placements_until(&moves, deadline);
+    let mut scored: Vec<((i32, i32), i32)> = moves.into_iter().zip(scores).collect();
+    scored.sort_by_key(|&(_, score)| Reverse(score));
+    scored.into_iter().map(|(placement, _)| placement).collect()
+}
+
+// Small shapes the engine deals most often, used to model future turns
+pub fn default_likely_pieces() -> Vec<Piece> {
+    let shapes: [&[&str]; 5] = [&["OO", "OO"], &["OOO"], &["O", "O"], &["O.", "OO"], &["O"]];
+    shapes
+        .iter()
+        .map(|rows| {
+            let pattern: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+            Piece::new(pattern[0].len(), pattern.len(), pattern)
+        })
+        .collect()
+}
+
+// Per-search data shared by every node
+struct SearchContext {
+    deadline: Deadline,
+    // likely_pieces compiled once for the whole search
+    compiled_pieces: Vec<Option<CompiledPiece>>,
+    // Distance from each cell to the nearest cell of the other player, used for move ordering
+    distance_to_opponent: DistanceField,
+    distance_to_me: DistanceField,
+}
+
+impl AlphaBetaSearch {
+    // Picks a move for the AI's current board and piece
+    // Returns None only when the current piece has no legal placement
+    pub fn find_move(&self, ai: &FillerAi) -> Option<(i32, i32)> {
+        // Separated players do not interact, so there is nothing to look ahead for
+        if ai.is_endgame() {
+            return ai.find_best_move();
+        }
+
+        let mut root_moves = ai.find_all_valid_placements();
+        if root_moves.is_empty() {
+            return None;
+        }
+
+        // Order root moves by the heuristic score so cut-offs happen early
+        // Moves not scored before the move deadline sort last
+        root_moves = order_by_score(ai, root_moves, ai.deadline);
+        root_moves.truncate(self.max_branching.max(1));
+        let deadline = Deadline::after(self.time_budget).min(ai.deadline);
+
+        let context = SearchContext {
+            deadline,
+            compiled_pieces: self.likely_pieces.iter().map(CompiledPiece::new).collect(),
+            distance_to_opponent: distance_map(&ai.cells, Side::Opponent),
+            distance_to_me: distance_map(&ai.cells, Side::Me),
+        };
+
+        let mut best_move = root_moves[0];
+        for depth in 1..=self.max_depth.max(1) {
+            let Some(found) = self.search_root(&context, ai, &root_moves, depth) else {
+                break; // Out of time: keep the result of the last completed depth
+            };
+            best_move = found;
+
+            // Search the previous best first at the next depth
+            if let Some(index) = root_moves.iter().position(|&mv| mv == found) {
+                root_moves[..=index].rotate_right(1);
+            }
+        }
+
+        Some(best_move)
+    }
+
+    // Runs one full-width search at the root; None if the deadline passed
+    fn search_root(
+        &self,
+        context: &SearchContext,
+        ai: &FillerAi,
+        root_moves: &[(i32, i32)],
+        depth: usize,
+    ) -> Option<(i32, i32)> {
+        let mut alpha = i32::MIN;
+        let mut best = None;
+
+        for &(x, y) in root_moves {
+            let mut position = ai.position();
+            position.place(
+                &ai.current_piece,
+                ai.compiled_piece.as_ref(),
+                x,
+                y,
+                Side::Me,
+            );
+
+            let score =
+                self.alpha_beta(context, &position, 1, depth - 1, alpha, i32::MAX, false)?;
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some((x, y));
+            }
+        }
+
+        best
+    }
+
+    // Minimax with alpha-beta pruning; scores are from our point of view
+    // Returns None when the deadline passes so the partial result is discarded
+    #[allow(clippy::too_many_arguments)]
+    fn alpha_beta(
+        &self,
+        context: &SearchContext,
+        position: &Position,
+        ply: usize,
+        depth: usize,
+        alpha: i32,
+        mut beta: i32,
+        maximizing: bool,
+    ) -> Option<i32> {
+        if context.deadline.expired() {
+            return None;
+        }
+        if depth == 0 || self.likely_pieces.is_empty() {
+            return Some(evaluate(&position.board));
+        }
+
+        if maximizing {
+            let index = (ply - 1) % self.likely_pieces.len();
+            return self.expand(context, position, index, ply, depth, alpha, beta, true);
+        }
+
+        // Any of the likely pieces may come next, so the opponent gets the one that
+        // hurts us most
+        let mut best = i32::MAX;
+        for index in 0..self.likely_pieces.len() {
+            let score = self.expand(context, position, index, ply, depth, alpha, beta, false)?;
+            best = best.min(score);
+            beta = beta.min(best);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        Some(best)
+    }
+
+    // Searches the mover's placements of one likely piece
+    #[allow(clippy::too_many_arguments)]
+    fn expand(
+        &self,
+        context: &SearchContext,
+        position: &Position,
+        index: usize,
+        ply: usize,
+        depth: usize,
+        mut alpha: i32,
+        mut beta: i32,
+        maximizing: bool,
+    ) -> Option<i32> {
+        let (piece, compiled) = (
+            &self.likely_pieces[index],
+            context.compiled_pieces[index].as_ref(),
+        );
+        let (mover, distances) = if maximizing {
+            (Side::Me, &context.distance_to_opponent)
+        } else {
+            (Side::Opponent, &context.distance_to_me)
+        };
+
+        let mut moves = position.valid_placements(piece, compiled, mover);
+        if moves.is_empty() {
+            // A blocked player simply passes in the model
+            return self.alpha_beta(
+                context,
+                position,
+                ply + 1,
+                depth - 1,
+                alpha,
+                beta,
+                !maximizing,
+            );
+        }
+
+        // Expand moves that push toward the other player first
+        moves.sort_by_cached_key(|&(x, y)| placement_distance(distances, piece, x, y));
+        moves.truncate(self.max_branching.max(1));
+
+        let mut best = if maximizing { i32::MIN } else { i32::MAX };
+        for (x, y) in moves {
+            let mut child = position.clone();
+            child.place(piece, compiled, x, y, mover);
+            let score = self.alpha_beta(
+                context,
+                &child,
+                ply + 1,
+                depth - 1,
+                alpha,
+                beta,
+                !maximizing,
+            )?;
+
+            if maximizing {
+                best = best.max(score);
+                alpha = alpha.max(best);
+            } else {
+                best = best.min(score);
+                beta = beta.min(best);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        Some(best)
+    }
+}
+
+impl Strategy for AlphaBetaSearch {
+    fn name(&self) -> &'static str {
+        "alphabeta"
+    }
+
+    fn choose_move(&mut self, ai: &FillerAi) -> Option<(i32, i32)> {
+        self.find_move(ai)
+    }
+}
+
+// Static evaluation from our side of the board: territory difference plus room to grow
+// Frontier = empty cells 4-adjacent to a player's territory
+pub fn evaluate(board: &Board) -> i32 {
+    let mut territory = 0;
+    let mut frontier = 0;
+    for (x, y, cell) in board.iter() {
+        if cell.is_mine() {
+            territory += 1;
+        } else if cell.is_theirs() {
+            territory -= 1;
+        } else {
+            let neighbours = || board.orthogonal_neighbours(x, y).map(|(_, _, cell)| cell);
+            if neighbours().any(|cell| cell.is_mine()) {
+                frontier += 1;
+            }
+            if neighbours().any(|cell| cell.is_theirs()) {
+                frontier -= 1;
+            }
+        }
+    }
+
+    territory + 2 * frontier
+}
+
+// BFS distance from every cell to the nearest cell owned by the side, through empty cells
+fn distance_map(board: &Board, side: Side) -> DistanceField {
+    DistanceField::compute(
+        board.width,
+        board.height,
+        |x, y| side.owns(board.cell(x, y)),
+        |x, y| board.cell(x, y).is_empty(),
+    )
+}
+
+// Sum of distances under the piece's solid cells; unreachable cells count as far away
+fn placement_distance(distances: &DistanceField, piece: &Piece, x: i32, y: i32) -> u64 {
+    let unreachable = (distances.width * distances.height) as u64;
+    piece
+        .solid_cells()
+        .iter()
+        .map(|&(piece_x, piece_y)| {
+            let board_x = (x + piece_x as i32) as usize;
+            let board_y = (y + piece_y as i32) as usize;
+            distances
+                .get(board_x, board_y)
+                .map_or(unreachable, u64::from)
+        })
+        .sum()
+}
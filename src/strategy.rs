@@ -0,0 +1,37 @@
+use crate::filler_ai::FillerAi;
+
+// Names accepted by strategy_from_name, the first one is the default
+pub const STRATEGY_NAMES: &[&str] = &["heuristic", "alphabeta", "mcts"];
+
+// Move selection policy
+// Reads the AI's current board, players and piece and returns the offset to play,
+// or None when the piece cannot be placed
+pub trait Strategy {
+    // Name used on the command line and in FILLER_STRATEGY
+    fn name(&self) -> &'static str;
+
+    fn choose_move(&mut self, ai: &FillerAi) -> Option<(i32, i32)>;
+}
+
+// The weighted heat/blocking/expansion/efficiency scorer in FillerAi
+pub struct HeuristicStrategy;
+
+impl Strategy for HeuristicStrategy {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    fn choose_move(&mut self, ai: &FillerAi) -> Option<(i32, i32)> {
+        ai.find_best_move()
+    }
+}
+
+// Builds a strategy from its name with default settings
+pub fn strategy_from_name(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "heuristic" => Some(Box::new(HeuristicStrategy)),
+        "alphabeta" => Some(Box::new(crate::search::AlphaBetaSearch::default())),
+        "mcts" => Some(Box::new(crate::mcts::MonteCarloSearch::default())),
+        _ => None,
+    }
+}
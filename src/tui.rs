@@ -0,0 +1,184 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::time::Duration;
+
+use crate::board::{Board, Cell};
+use crate::palette::{BACKGROUND, EMPTY_COLOR, PLACED_OUTLINE, Rgb, cell_color};
+use crate::piece::Piece;
+
+// Moves the cursor home and clears the screen, so each turn redraws in place
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+const RESET: &str = "\x1b[0m";
+
+// Draws every turn in the terminal with ANSI colours, for games where no SDL window
+// can open (the engine's Docker container)
+// Output goes to stderr or a side file, never stdout, which belongs to the engine;
+// a file can be watched from another shell with `tail -f`
+pub struct TuiRenderer<W: Write> {
+    out: W,
+    turn: usize,
+}
+
+impl<W: Write> TuiRenderer<W> {
+    pub fn new(out: W) -> Self {
+        Self { out, turn: 0 }
+    }
+
+    // Draws the board as received, with the cells of our answer marked
+    pub fn draw(
+        &mut self,
+        board: &Board,
+        my_player_number: u8,
+        piece: &Piece,
+        answer: Option<(i32, i32)>,
+        elapsed: Duration,
+    ) -> io::Result<()> {
+        self.turn += 1;
+        let frame = render_frame(board, my_player_number, piece, answer, elapsed, self.turn);
+        self.out.write_all(CLEAR_SCREEN.as_bytes())?;
+        self.out.write_all(frame.as_bytes())?;
+        self.out.flush()
+    }
+}
+
+impl TuiRenderer<Box<dyn Write + Send>> {
+    // "stderr" (or "-") draws to stderr, anything else is a file that is truncated
+    pub fn open(target: &str) -> io::Result<Self> {
+        let out: Box<dyn Write + Send> = match target {
+            "stderr" | "-" => Box::new(io::stderr()),
+            path => Box::new(BufWriter::new(File::create(path)?)),
+        };
+        Ok(Self::new(out))
+    }
+}
+
+// One turn as text: a status line, the board two columns per cell, the cell count
+// of each player, then the piece
+pub fn render_frame(
+    board: &Board,
+    my_player_number: u8,
+    piece: &Piece,
+    answer: Option<(i32, i32)>,
+    elapsed: Duration,
+    turn: usize,
+) -> String {
+    let opponent_number = if my_player_number == 1 { 2 } else { 1 };
+    let placed: Vec<(usize, usize)> = answer
+        .map(|(x, y)| {
+            piece
+                .solid_cells()
+                .into_iter()
+                .map(|(px, py)| (x + px as i32, y + py as i32))
+                .filter(|&(cx, cy)| board.in_bounds(cx, cy))
+                .map(|(cx, cy)| (cx as usize, cy as usize))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut frame = format!(
+        "Filler p{} - turn {} - {} in {:.3} ms\n",
+        my_player_number,
+        turn,
+        match answer {
+            Some((x, y)) => format!("move {} {}", x, y),
+            None => "no move".to_string(),
+        },
+        elapsed.as_secs_f64() * 1000.0
+    );
+
+    for y in 0..board.height {
+        let mut line = Line::new(&mut frame);
+        for x in 0..board.width {
+            let color = cell_color(board.cell(x, y), my_player_number);
+            if placed.contains(&(x, y)) {
+                line.push("[]", Some(color), Some(PLACED_OUTLINE));
+            } else {
+                line.push("  ", Some(color), None);
+            }
+        }
+        line.end();
+    }
+
+    let count = |owned: fn(Cell) -> bool| board.iter().filter(|&(_, _, cell)| owned(cell)).count();
+    let mut line = Line::new(&mut frame);
+    line.push("  ", Some(cell_color(Cell::Mine, my_player_number)), None);
+    line.push(
+        &format!(" p{} (me) {}   ", my_player_number, count(Cell::is_mine)),
+        None,
+        None,
+    );
+    line.push("  ", Some(cell_color(Cell::Theirs, my_player_number)), None);
+    line.push(
+        &format!(" p{} {}   ", opponent_number, count(Cell::is_theirs)),
+        None,
+        None,
+    );
+    line.push("  ", Some(EMPTY_COLOR), None);
+    line.push(&format!(" empty {}", count(Cell::is_empty)), None, None);
+    line.end();
+
+    frame.push_str(&format!("piece {}x{}\n", piece.width, piece.height));
+    let mine = cell_color(Cell::Mine, my_player_number);
+    for row in &piece.pattern {
+        let mut line = Line::new(&mut frame);
+        for &symbol in row {
+            let color = if symbol == '.' { BACKGROUND } else { mine };
+            line.push("  ", Some(color), None);
+        }
+        line.end();
+    }
+
+    frame
+}
+
+// One line of the frame being written; colours are only sent when they change, and
+// reset once at the end of the line, so a wide board does not repeat an escape per cell
+struct Line<'a> {
+    frame: &'a mut String,
+    // Colours currently set in the terminal; None is the terminal's default
+    background: Option<Rgb>,
+    foreground: Option<Rgb>,
+}
+
+impl<'a> Line<'a> {
+    fn new(frame: &'a mut String) -> Self {
+        Self {
+            frame,
+            background: None,
+            foreground: None,
+        }
+    }
+
+    // Appends text in the given colours, None for the terminal's default
+    fn push(&mut self, text: &str, background: Option<Rgb>, foreground: Option<Rgb>) {
+        if background != self.background || foreground != self.foreground {
+            if background.is_none() && foreground.is_none() {
+                self.frame.push_str(RESET);
+            } else {
+                if background != self.background {
+                    self.frame.push_str(&match background {
+                        Some(c) => format!("\x1b[48;2;{};{};{}m", c.r, c.g, c.b),
+                        None => "\x1b[49m".to_string(),
+                    });
+                }
+                if foreground != self.foreground {
+                    self.frame.push_str(&match foreground {
+                        Some(c) => format!("\x1b[38;2;{};{};{}m", c.r, c.g, c.b),
+                        None => "\x1b[39m".to_string(),
+                    });
+                }
+            }
+            self.background = background;
+            self.foreground = foreground;
+        }
+        self.frame.push_str(text);
+    }
+
+    // Resets the colours if any are set and ends the line
+    fn end(self) {
+        if self.background.is_some() || self.foreground.is_some() {
+            self.frame.push_str(RESET);
+        }
+        self.frame.push('\n');
+    }
+}
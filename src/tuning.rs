@@ -0,0 +1,225 @@
+use crate::config::ScoringConfig;
+use crate::filler_ai::FillerAi;
+use crate::map::{Map, MapGeneratorConfig, generate_map};
+use crate::referee::Referee;
+use crate::rng::Rng;
+use crate::strategy::HeuristicStrategy;
+
+// Settings for the self-play weight optimizer
+#[derive(Clone, Debug)]
+pub struct TuningConfig {
+    pub population: usize,
+    pub generations: usize,
+    // Games each candidate plays against the baseline per generation
+    pub games_per_candidate: usize,
+    // Best candidates copied unchanged into the next generation
+    pub elites: usize,
+    // Relative size of a mutation step, e.g. 0.25 = up to +-25% of the value
+    pub mutation_scale: f64,
+    // Best candidates of each generation re-scored on the held-out games
+    pub finalists: usize,
+    // Games on held-out seeds, the same for every finalist, that pick the winner
+    pub validation_games: usize,
+    pub maps: MapGeneratorConfig,
+    pub seed: u64,
+}
+
+impl Default for TuningConfig {
+    fn default() -> Self {
+        Self {
+            population: 12,
+            generations: 10,
+            games_per_candidate: 6,
+            elites: 2,
+            mutation_scale: 0.25,
+            finalists: 2,
+            validation_games: 12,
+            maps: MapGeneratorConfig {
+                min_width: 15,
+                max_width: 40,
+                min_height: 15,
+                max_height: 30,
+                min_separation: 0.4,
+            },
+            seed: 0,
+        }
+    }
+}
+
+impl TuningConfig {
+    // Seed of the held-out games; generation games start at `seed` and step by 7919,
+    // so they stay clear of this range
+    pub fn validation_seed(&self) -> u64 {
+        self.seed.wrapping_add(1 << 40)
+    }
+}
+
+// A candidate weight vector and its measured fitness
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub scoring: ScoringConfig,
+    // Share of the cells both players claimed that went to the candidate, in [0, 1]
+    pub fitness: f64,
+}
+
+// Plays seeded self-play games of `scoring` against `baseline` and returns the candidate's
+// share of claimed cells. Games come in pairs on the same map and pieces with the seats
+// swapped, so neither side keeps the first-move or map advantage.
+pub fn evaluate_scoring(
+    scoring: &ScoringConfig,
+    baseline: &ScoringConfig,
+    games: usize,
+    maps: &MapGeneratorConfig,
+    seed: u64,
+) -> f64 {
+    let mut own_cells = 0;
+    let mut total_cells = 0;
+
+    for game in 0..games {
+        let game_seed = seed.wrapping_add((game / 2) as u64);
+        let map: Map = generate_map(maps, game_seed);
+        let mut referee = Referee::new(&map, game_seed);
+
+        let candidate_seat = game % 2;
+        let mut ais = [FillerAi::new(1), FillerAi::new(2)];
+        ais[candidate_seat].scoring = scoring.clone();
+        ais[1 - candidate_seat].scoring = baseline.clone();
+
+        let (first, second) =
+            referee.play_in_process(&mut ais, [&mut HeuristicStrategy, &mut HeuristicStrategy]);
+        let scores = [first, second];
+        own_cells += scores[candidate_seat];
+        total_cells += first + second;
+    }
+
+    if total_cells == 0 {
+        0.5
+    } else {
+        own_cells as f64 / total_cells as f64
+    }
+}
+
+// Evolves scoring weights with a genetic algorithm
+// Every generation plays on fresh seeded maps, keeps the elites, and fills the rest of
+// the population with mutated crossovers of tournament-selected parents.
+// Generations play different maps, so their fitness does not compare: the finalists of
+// every generation are re-scored on the same held-out games, and the best there wins.
+// `on_generation` receives the generation index and its best candidate; the returned
+// candidate carries its held-out fitness.
+pub fn evolve(
+    config: &TuningConfig,
+    baseline: &ScoringConfig,
+    mut on_generation: impl FnMut(usize, &Candidate),
+) -> Candidate {
+    let mut rng = Rng::new(config.seed);
+    let population_size = config.population.max(2);
+
+    // Start from the baseline plus mutated copies of it
+    let mut population: Vec<ScoringConfig> = (0..population_size)
+        .map(|index| {
+            if index == 0 {
+                baseline.clone()
+            } else {
+                mutate(baseline, config.mutation_scale * 2.0, &mut rng)
+            }
+        })
+        .collect();
+    let mut finalists: Vec<Candidate> = Vec::new();
+
+    for generation in 0..config.generations.max(1) {
+        let game_seed = config
+            .seed
+            .wrapping_add((generation * config.games_per_candidate) as u64 * 7919);
+
+        let mut ranked: Vec<Candidate> = population
+            .iter()
+            .map(|scoring| Candidate {
+                scoring: scoring.clone(),
+                fitness: evaluate_scoring(
+                    scoring,
+                    baseline,
+                    config.games_per_candidate,
+                    &config.maps,
+                    game_seed,
+                ),
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+        on_generation(generation, &ranked[0]);
+        for candidate in ranked.iter().take(config.finalists.max(1)) {
+            // Elites come back unchanged; their held-out games are already played
+            if finalists.iter().any(|f| f.scoring == candidate.scoring) {
+                continue;
+            }
+            finalists.push(Candidate {
+                scoring: candidate.scoring.clone(),
+                fitness: evaluate_scoring(
+                    &candidate.scoring,
+                    baseline,
+                    config.validation_games.max(1),
+                    &config.maps,
+                    config.validation_seed(),
+                ),
+            });
+        }
+
+        // Next generation: elites first, then children
+        let mut next: Vec<ScoringConfig> = ranked
+            .iter()
+            .take(config.elites.min(population_size))
+            .map(|c| c.scoring.clone())
+            .collect();
+        while next.len() < population_size {
+            let mother = tournament(&ranked, &mut rng);
+            let father = tournament(&ranked, &mut rng);
+            let child = crossover(&mother.scoring, &father.scoring, &mut rng);
+            next.push(mutate(&child, config.mutation_scale, &mut rng));
+        }
+        population = next;
+    }
+
+    // The earliest finalist wins ties
+    finalists
+        .into_iter()
+        .reduce(|best, candidate| {
+            if candidate.fitness > best.fitness {
+                candidate
+            } else {
+                best
+            }
+        })
+        .expect("at least one generation runs")
+}
+
+// Picks the fitter of two random candidates
+fn tournament<'a>(ranked: &'a [Candidate], rng: &mut Rng) -> &'a Candidate {
+    let a = &ranked[rng.range(0, ranked.len() - 1)];
+    let b = &ranked[rng.range(0, ranked.len() - 1)];
+    if a.fitness >= b.fitness { a } else { b }
+}
+
+// Takes each weight from one of the two parents at random
+fn crossover(mother: &ScoringConfig, father: &ScoringConfig, rng: &mut Rng) -> ScoringConfig {
+    let mut child = mother.clone();
+    for key in ScoringConfig::KEYS {
+        if rng.next_u64() % 2 == 1 {
+            let value = father.get(key).unwrap_or_default();
+            let _ = child.set(key, &value.to_string());
+        }
+    }
+    child
+}
+
+// Nudges every weight by a random step proportional to its size; weights stay >= 0
+pub fn mutate(scoring: &ScoringConfig, scale: f64, rng: &mut Rng) -> ScoringConfig {
+    let mut mutated = scoring.clone();
+    for key in ScoringConfig::KEYS {
+        let value = scoring.get(key).unwrap_or_default() as f64;
+        let step = (value.abs() * scale).max(1.0);
+        let delta = (rng.next_f64() * 2.0 - 1.0) * step;
+        let new_value = (value + delta).round().max(0.0) as i32;
+        let _ = mutated.set(key, &new_value.to_string());
+    }
+    mutated
+}
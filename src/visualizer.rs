@@ -1,18 +1,31 @@
+use crate::board::Board;
+use crate::filler_ai::FillerAi;
+use crate::overlay::{Overlay, ReplayAnalysis};
+use crate::palette::{
+    BACKGROUND, BORDER_COLOR, CHOKEPOINT_OUTLINE, EMPTY_COLOR, HEAT_COLOR, PLACED_OUTLINE, Rgb,
+    SCORE_HIGH, SCORE_LOW, blend, cell_color,
+};
+use crate::replay::{Replay, ReplayCursor};
 use sdl2::EventPump;
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::render::WindowCanvas;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
+use std::time::{Duration, Instant};
 
-// Color scheme for the game
 const WINDOW_SIZE: u32 = 1000;
-const BORDER_COLOR: Color = Color::RGB(64, 64, 64);
-const EMPTY_COLOR: Color = Color::RGB(200, 200, 200);
-const PLAYER1_TERRITORY: Color = Color::RGB(255, 100, 100); // Red
-const PLAYER1_RECENT: Color = Color::RGB(255, 50, 50); // Bright Red
-const PLAYER2_TERRITORY: Color = Color::RGB(100, 100, 255); // Blue
-const PLAYER2_RECENT: Color = Color::RGB(50, 50, 255); // Bright Blue
+
+// Replay viewer: time between turns while playing, and turns skipped by Up/Down
+const AUTOPLAY_DELAY: Duration = Duration::from_millis(250);
+const REPLAY_JUMP: isize = 10;
+
+fn sdl_color(color: Rgb) -> Color {
+    Color::RGB(color.r, color.g, color.b)
+}
 
 pub struct GameVisualizer {
     canvas: WindowCanvas,
@@ -21,6 +34,9 @@ pub struct GameVisualizer {
     cell_height: f32,
     board_width: usize,
     board_height: usize,
+    // Overlay layers toggled with H (heat) and S (candidate scores)
+    show_heat: bool,
+    show_scores: bool,
 }
 
 impl GameVisualizer {
@@ -44,6 +60,8 @@ impl GameVisualizer {
             cell_height: 0.0,
             board_width: 0,
             board_height: 0,
+            show_heat: false,
+            show_scores: false,
         })
     }
 
@@ -54,15 +72,24 @@ impl GameVisualizer {
         self.cell_height = WINDOW_SIZE as f32 / height as f32;
     }
 
-    pub fn render_board(&mut self, board: &Vec<Vec<char>>) -> Result<(), String> {
-        // Clear canvas with background color
-        self.canvas.set_draw_color(Color::RGB(240, 240, 240));
-        self.canvas.clear();
-
-        // Draw each cell
-        for (row_idx, row) in board.iter().enumerate() {
-            for (col_idx, &cell) in row.iter().enumerate() {
-                self.draw_cell(col_idx, row_idx, cell)?;
+    // Draws the board; my_player_number keeps player 1 red and player 2 blue
+    // whichever side the AI is playing
+    // With the heat layer on, contested chokepoints are outlined; with the score
+    // layer on, the cells of the chosen move are
+    pub fn render_board(
+        &mut self,
+        board: &Board,
+        my_player_number: u8,
+        overlay: Option<&Overlay>,
+    ) -> Result<(), String> {
+        self.update_board_dimensions(board.width, board.height);
+        self.draw_board(board, my_player_number, overlay)?;
+        self.outline_chokepoints(overlay)?;
+        if self.show_scores
+            && let Some(overlay) = overlay
+        {
+            for &(col, row) in &overlay.chosen {
+                self.outline_cell(col, row, PLACED_OUTLINE)?;
             }
         }
 
@@ -71,60 +98,277 @@ impl GameVisualizer {
         Ok(())
     }
 
-    fn draw_cell(&mut self, col: usize, row: usize, cell_char: char) -> Result<(), String> {
+    // Draws a replayed turn: the board the AI received, with the cells of the
+    // piece it answered outlined
+    pub fn render_replay_turn(
+        &mut self,
+        board: &Board,
+        my_player_number: u8,
+        placed: &[(usize, usize)],
+        overlay: Option<&Overlay>,
+    ) -> Result<(), String> {
+        self.update_board_dimensions(board.width, board.height);
+        self.draw_board(board, my_player_number, overlay)?;
+        self.outline_chokepoints(overlay)?;
+        for &(col, row) in placed {
+            self.outline_cell(col, row, PLACED_OUTLINE)?;
+        }
+
+        self.canvas.present();
+        Ok(())
+    }
+
+    pub fn set_title(&mut self, title: &str) -> Result<(), String> {
+        self.canvas
+            .window_mut()
+            .set_title(title)
+            .map_err(|e| e.to_string())
+    }
+
+    // Whether a layer is shown, so the overlay only gets computed when it is seen
+    pub fn wants_overlay(&self) -> bool {
+        self.show_heat || self.show_scores
+    }
+
+    // H shows or hides the heat layer, S the score layer
+    // Returns whether the key was one of them
+    fn toggle_overlay(&mut self, key: Keycode) -> bool {
+        match key {
+            Keycode::H => self.show_heat = !self.show_heat,
+            Keycode::S => self.show_scores = !self.show_scores,
+            _ => return false,
+        }
+        true
+    }
+
+    fn draw_board(
+        &mut self,
+        board: &Board,
+        my_player_number: u8,
+        overlay: Option<&Overlay>,
+    ) -> Result<(), String> {
+        // Clear canvas with background color
+        self.canvas.set_draw_color(sdl_color(BACKGROUND));
+        self.canvas.clear();
+
+        // Draw each cell
+        for (col_idx, row_idx, cell) in board.iter() {
+            let color = overlay
+                .and_then(|overlay| self.overlay_color(overlay, col_idx, row_idx))
+                .unwrap_or_else(|| cell_color(cell, my_player_number));
+            self.draw_cell(col_idx, row_idx, color)?;
+        }
+        Ok(())
+    }
+
+    // Colour of an empty cell under the visible layers; scores win over heat
+    fn overlay_color(&self, overlay: &Overlay, col: usize, row: usize) -> Option<Rgb> {
+        let heat = overlay.heat(col, row)?;
+        if self.show_scores
+            && let Some(score) = overlay.score(col, row)
+        {
+            return Some(blend(SCORE_LOW, SCORE_HIGH, score));
+        }
+        self.show_heat.then(|| blend(EMPTY_COLOR, HEAT_COLOR, heat))
+    }
+
+    fn draw_cell(&mut self, col: usize, row: usize, color: Rgb) -> Result<(), String> {
         let x = (col as f32 * self.cell_width) as i32;
         let y = (row as f32 * self.cell_height) as i32;
         let width = self.cell_width as u32;
         let height = self.cell_height as u32;
 
-        // Determine cell color based on character
-        let color = match cell_char {
-            '.' => EMPTY_COLOR,
-            '@' => PLAYER1_TERRITORY,
-            'a' => PLAYER1_RECENT,
-            '$' => PLAYER2_TERRITORY,
-            's' => PLAYER2_RECENT,
-            _ => EMPTY_COLOR, // Fallback for unknown characters
-        };
-
         // Fill the cell with the appropriate color
-        self.canvas.set_draw_color(color);
+        self.canvas.set_draw_color(sdl_color(color));
         let cell_rect = Rect::new(x + 1, y + 1, width - 2, height - 2);
         self.canvas.fill_rect(cell_rect)?;
 
         // Draw border around the cell
-        self.canvas.set_draw_color(BORDER_COLOR);
+        self.canvas.set_draw_color(sdl_color(BORDER_COLOR));
         let border_rect = Rect::new(x, y, width, height);
         self.canvas.draw_rect(border_rect)?;
 
         Ok(())
     }
 
-    pub fn handle_events(&mut self) -> bool {
-        for event in self.event_pump.poll_iter() {
+    fn outline_chokepoints(&mut self, overlay: Option<&Overlay>) -> Result<(), String> {
+        if self.show_heat
+            && let Some(overlay) = overlay
+        {
+            for &(col, row) in &overlay.chokepoints {
+                self.outline_cell(col, row, CHOKEPOINT_OUTLINE)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Two-pixel frame inside the cell's border
+    fn outline_cell(&mut self, col: usize, row: usize, color: Rgb) -> Result<(), String> {
+        let x = (col as f32 * self.cell_width) as i32;
+        let y = (row as f32 * self.cell_height) as i32;
+        let width = self.cell_width as u32;
+        let height = self.cell_height as u32;
+
+        self.canvas.set_draw_color(sdl_color(color));
+        self.canvas.draw_rect(Rect::new(x, y, width, height))?;
+        self.canvas
+            .draw_rect(Rect::new(x + 1, y + 1, width - 2, height - 2))?;
+        Ok(())
+    }
+
+    // Returns None once the window is closed, otherwise whether a layer was toggled
+    pub fn handle_events(&mut self) -> Option<bool> {
+        let mut toggled = false;
+        for event in self.event_pump.poll_iter().collect::<Vec<_>>() {
             match event {
-                sdl2::event::Event::Quit { .. } => return false,
+                Event::Quit { .. } => return None,
+                Event::KeyDown {
+                    keycode: Some(key), ..
+                } => toggled |= self.toggle_overlay(key),
                 _ => {}
             }
         }
-        true
+        Some(toggled)
+    }
+}
+
+// Opens a window on a recorded game and steps through it from the keyboard
+// Right/Left: one turn, Down/Up (or Page Down/Page Up): ten turns, Home/End: first
+// and last turn, Space: play or pause, H/S: heat and score layers, Escape or Q: close
+// The layers are rebuilt by feeding the recorded boards to a fresh AI with the
+// default weights
+pub fn run_replay_viewer(replay: &Replay) -> Result<(), String> {
+    if replay.turns.is_empty() {
+        return Err("the replay has no turns".to_string());
+    }
+
+    let mut visualizer = GameVisualizer::new()?;
+    let mut analysis = ReplayAnalysis::new(replay);
+    let mut cursor = ReplayCursor::new(replay.turns.len());
+    let mut playing = false;
+    let mut next_step = Instant::now();
+    let mut redraw = true;
+
+    loop {
+        if redraw {
+            let turn = &replay.turns[cursor.turn];
+            let overlay = visualizer
+                .wants_overlay()
+                .then(|| analysis.overlay(cursor.turn));
+            visualizer.render_replay_turn(
+                &replay.cells(cursor.turn),
+                replay.player_number,
+                &turn.placed_cells(),
+                overlay.as_ref(),
+            )?;
+            visualizer.set_title(&replay_title(replay, cursor.turn, playing))?;
+            redraw = false;
+        }
+
+        let timeout = if playing {
+            next_step.saturating_duration_since(Instant::now())
+        } else {
+            Duration::from_millis(100)
+        };
+        match visualizer
+            .event_pump
+            .wait_event_timeout(timeout.as_millis() as u32)
+        {
+            Some(Event::Quit { .. }) => return Ok(()),
+            Some(Event::KeyDown {
+                keycode: Some(key), ..
+            }) => {
+                redraw = match key {
+                    Keycode::ESCAPE | Keycode::Q => return Ok(()),
+                    Keycode::RIGHT => cursor.step(1),
+                    Keycode::LEFT => cursor.step(-1),
+                    Keycode::DOWN | Keycode::PAGEDOWN => cursor.step(REPLAY_JUMP),
+                    Keycode::UP | Keycode::PAGEUP => cursor.step(-REPLAY_JUMP),
+                    Keycode::HOME => cursor.first(),
+                    Keycode::END => cursor.last(),
+                    Keycode::SPACE => {
+                        playing = !playing;
+                        // Playing from the last turn starts over
+                        if playing && cursor.at_end() {
+                            cursor.first();
+                        }
+                        next_step = Instant::now() + AUTOPLAY_DELAY;
+                        true
+                    }
+                    key => visualizer.toggle_overlay(key),
+                };
+            }
+            // The window was exposed, resized or restored
+            Some(Event::Window { .. }) => redraw = true,
+            _ => {}
+        }
+
+        if playing && Instant::now() >= next_step {
+            cursor.step(1);
+            playing = !cursor.at_end();
+            next_step += AUTOPLAY_DELAY;
+            redraw = true;
+        }
     }
 }
 
+// "Filler replay p1 - turn 12/154 - move 3 4 in 0.112 ms"
+fn replay_title(replay: &Replay, turn: usize, playing: bool) -> String {
+    let answer = match replay.turns[turn].answer {
+        Some((x, y)) => format!("move {} {}", x, y),
+        None => "no move".to_string(),
+    };
+    format!(
+        "Filler replay p{} - turn {}/{} - {} in {:.3} ms{}",
+        replay.player_number,
+        turn + 1,
+        replay.turns.len(),
+        answer,
+        replay.turns[turn].elapsed.as_secs_f64() * 1000.0,
+        if playing { " - playing" } else { "" }
+    )
+}
+
+// What the game hands over for a turn: the board with the AI's player number, or
+// a copy of the AI and its answer when a layer is shown, so the overlay is scored
+// here instead of on the game's clock
+enum Frame {
+    Board(Board, u8),
+    Turn(Box<FillerAi>, Option<(i32, i32)>),
+}
+
+impl Frame {
+    fn into_shown(self) -> (Board, u8, Option<Overlay>) {
+        match self {
+            Frame::Board(board, my_player_number) => (board, my_player_number, None),
+            Frame::Turn(ai, answer) => {
+                let overlay = Overlay::from_ai(&ai, answer);
+                (ai.cells, ai.my_player.number, Some(overlay))
+            }
+        }
+    }
+}
+
+// Latest frame waiting to be drawn; one slot, so an overlay never lands on
+// another turn's board
+type PendingFrame = Arc<Mutex<Option<Frame>>>;
+
 // Thread-safe wrapper for the visualizer
+#[derive(Default)]
 pub struct VisualizerHandle {
-    board_data: Arc<Mutex<Option<(usize, usize, Vec<Vec<char>>)>>>,
+    board_data: PendingFrame,
+    // Set while a layer is shown, so the game only sends its AI when someone looks
+    overlay_wanted: Arc<AtomicBool>,
 }
 
 impl VisualizerHandle {
     pub fn new() -> Self {
-        Self {
-            board_data: Arc::new(Mutex::new(None)),
-        }
+        Self::default()
     }
 
     pub fn start_visualizer(&self) {
         let board_data = self.board_data.clone();
+        let overlay_wanted = self.overlay_wanted.clone();
 
         thread::spawn(move || {
             let mut visualizer = match GameVisualizer::new() {
@@ -135,20 +379,32 @@ impl VisualizerHandle {
                 }
             };
 
-            let mut running = true;
-            while running {
+            // Kept to redraw the same turn when a layer is toggled
+            let mut shown = None;
+            loop {
+                let mut redraw = false;
+
                 // Check for new board data
-                if let Ok(mut data) = board_data.try_lock() {
-                    if let Some((width, height, board)) = data.take() {
-                        visualizer.update_board_dimensions(width, height);
-                        if let Err(e) = visualizer.render_board(&board) {
-                            eprintln!("Failed to render board: {}", e);
-                        }
-                    }
+                let frame = board_data.try_lock().ok().and_then(|mut data| data.take());
+                if let Some(frame) = frame {
+                    shown = Some(frame.into_shown());
+                    redraw = true;
                 }
 
                 // Handle SDL events
-                running = visualizer.handle_events();
+                match visualizer.handle_events() {
+                    None => break,
+                    Some(toggled) => redraw |= toggled,
+                }
+                overlay_wanted.store(visualizer.wants_overlay(), Ordering::Relaxed);
+
+                if redraw
+                    && let Some((board, my_player_number, overlay)) = &shown
+                    && let Err(e) =
+                        visualizer.render_board(board, *my_player_number, overlay.as_ref())
+                {
+                    eprintln!("Failed to render board: {}", e);
+                }
 
                 // Small delay to prevent busy waiting
                 std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
@@ -156,15 +412,28 @@ impl VisualizerHandle {
         });
     }
 
-    pub fn update_board(&self, width: usize, height: usize, board: Vec<Vec<char>>) {
+    pub fn update_board(&self, board: &Board, my_player_number: u8) {
+        self.send(Frame::Board(board.clone(), my_player_number));
+    }
+
+    // The AI as it answered a turn; the overlay is built from it on the
+    // visualizer thread
+    pub fn update_turn(&self, ai: FillerAi, answer: Option<(i32, i32)>) {
+        self.send(Frame::Turn(Box::new(ai), answer));
+    }
+
+    fn send(&self, frame: Frame) {
         if let Ok(mut data) = self.board_data.lock() {
-            *data = Some((width, height, board));
+            *data = Some(frame);
         }
     }
+
+    pub fn wants_overlay(&self) -> bool {
+        self.overlay_wanted.load(Ordering::Relaxed)
+    }
 }
 
 // Global visualizer instance (optional - for easy access)
-use std::sync::OnceLock;
 static VISUALIZER: OnceLock<VisualizerHandle> = OnceLock::new();
 
 pub fn get_visualizer() -> &'static VisualizerHandle {
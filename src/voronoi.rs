@@ -0,0 +1,103 @@
+use crate::board::{Board, Side};
+use crate::distance::DistanceField;
+use crate::piece::Piece;
+use crate::rules::apply_placement;
+
+// Empty cells split by which player reaches them first
+// Cells neither player can reach are not counted
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TerritoryControl {
+    pub mine: usize,
+    pub theirs: usize,
+    // Reached by both players at the same distance
+    pub contested: usize,
+}
+
+impl TerritoryControl {
+    // Counts one empty cell given the distance from each side
+    fn add(&mut self, mine: Option<u32>, theirs: Option<u32>, sign: isize) {
+        let slot = match (mine, theirs) {
+            (Some(a), Some(b)) if a < b => &mut self.mine,
+            (Some(a), Some(b)) if a > b => &mut self.theirs,
+            (Some(_), Some(_)) => &mut self.contested,
+            (Some(_), None) => &mut self.mine,
+            (None, Some(_)) => &mut self.theirs,
+            (None, None) => return,
+        };
+        *slot = slot.wrapping_add_signed(sign);
+    }
+
+    // Cells we reach first minus cells the opponent reaches first
+    pub fn margin(&self) -> i32 {
+        self.mine as i32 - self.theirs as i32
+    }
+}
+
+// Voronoi partition of the empty cells between the two players, from our side
+// Both territories expand one step per round through empty cells (a BFS from each
+// side); a cell belongs to whoever arrives first
+pub fn territory_control(board: &Board) -> TerritoryControl {
+    let distances = |side: Side| {
+        DistanceField::compute(
+            board.width,
+            board.height,
+            |x, y| side.owns(board.cell(x, y)),
+            |x, y| board.cell(x, y).is_empty(),
+        )
+    };
+    territory_from_fields(board, &distances(Side::Me), &distances(Side::Opponent))
+}
+
+// Territory control of a typed board from distance fields that are already built
+pub fn territory_from_fields(
+    board: &Board,
+    mine: &DistanceField,
+    theirs: &DistanceField,
+) -> TerritoryControl {
+    let mut control = TerritoryControl::default();
+    for (x, y, cell) in board.iter() {
+        if cell.is_empty() {
+            control.add(mine.get(x, y), theirs.get(x, y), 1);
+        }
+    }
+    control
+}
+
+// Territory control after we claim the empty cells in `placed`, patched from the
+// current board's distance fields and control instead of two fresh BFS
+// Only cells whose distance changes are recounted
+pub fn territory_after_fill(
+    board: &Board,
+    mine: &DistanceField,
+    theirs: &DistanceField,
+    control: TerritoryControl,
+    placed: &[(usize, usize)],
+) -> TerritoryControl {
+    let is_open = |x: usize, y: usize| board.cell(x, y).is_empty() && !placed.contains(&(x, y));
+
+    let mut next_mine = mine.clone();
+    let mut next_theirs = theirs.clone();
+    let mut changed = next_mine.add_sources(placed, is_open);
+    changed.extend(next_theirs.close(placed, |x, y| board.cell(x, y).is_theirs(), is_open));
+    changed.sort_unstable_by_key(|&(x, y)| (y, x));
+    changed.dedup();
+
+    let mut control = control;
+    for (x, y) in changed {
+        if !board.cell(x, y).is_empty() {
+            continue;
+        }
+        control.add(mine.get(x, y), theirs.get(x, y), -1);
+        if is_open(x, y) {
+            control.add(next_mine.get(x, y), next_theirs.get(x, y), 1);
+        }
+    }
+    control
+}
+
+// Territory control on the hypothetical board after we place the piece at (x, y)
+pub fn territory_after_placement(board: &Board, piece: &Piece, x: i32, y: i32) -> TerritoryControl {
+    let mut next = board.clone();
+    apply_placement(&mut next, piece, x, y, Side::Me);
+    territory_control(&next)
+}
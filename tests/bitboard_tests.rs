@@ -0,0 +1,144 @@
+mod common;
+
+use common::{board, random_board};
+use filler::bitboard::{Bitboard, CompiledPiece};
+use filler::board::Side;
+use filler::piece::{Piece, PieceGenerator, PieceGeneratorConfig};
+use filler::rng::Rng;
+use filler::rules::{Position, scan_valid_placements, valid_placements};
+
+#[test]
+fn test_cell_masks() {
+    let board = board(&["@a.", "$s."]);
+    let bitboard = Bitboard::from_board(&board).unwrap();
+
+    assert!(bitboard.is_mine(0, 0) && bitboard.is_mine(1, 0));
+    assert!(bitboard.is_theirs(0, 1) && bitboard.is_theirs(1, 1));
+    assert!(bitboard.is_empty(2, 0) && bitboard.is_empty(2, 1));
+    assert!(!bitboard.is_empty(0, 0));
+    assert!(!bitboard.is_mine(5, 0));
+}
+
+#[test]
+fn test_compiled_piece_rows_and_bounds() {
+    let piece = Piece::new(
+        3,
+        3,
+        vec![
+            vec!['.', '.', '.'],
+            vec!['.', 'O', 'O'],
+            vec!['.', 'O', '.'],
+        ],
+    );
+    let compiled = CompiledPiece::new(&piece).unwrap();
+
+    assert_eq!(compiled.rows, vec![0b000, 0b110, 0b010]);
+    let bounds = compiled.solid_bounds.unwrap();
+    assert_eq!((bounds.min_x, bounds.max_x), (1, 2));
+    assert_eq!((bounds.min_y, bounds.max_y), (1, 2));
+}
+
+#[test]
+fn test_overlap_count() {
+    let board = board(&["@@..", "....", "...$"]);
+    let bitboard = Bitboard::from_board(&board).unwrap();
+    let piece = CompiledPiece::new(&Piece::new(2, 1, vec![vec!['O', 'O']])).unwrap();
+
+    assert_eq!(bitboard.overlap_count(&piece, 0, 0, Side::Me), Some(2));
+    assert_eq!(bitboard.overlap_count(&piece, 1, 0, Side::Me), Some(1));
+    assert_eq!(bitboard.overlap_count(&piece, 2, 2, Side::Me), None);
+    assert_eq!(bitboard.overlap_count(&piece, 3, 0, Side::Me), None);
+    // From the other side our cells block and theirs count
+    assert_eq!(bitboard.overlap_count(&piece, 0, 0, Side::Opponent), None);
+    assert_eq!(
+        bitboard.overlap_count(&piece, 2, 2, Side::Opponent),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_negative_offsets() {
+    let board = board(&["@...", "....", "...$"]);
+    let piece = Piece::new(2, 2, vec![vec!['.', '.'], vec!['.', 'O']]);
+    let bitboard = Bitboard::from_board(&board).unwrap();
+
+    assert_eq!(
+        bitboard.valid_placements(&CompiledPiece::new(&piece).unwrap(), Side::Me),
+        vec![(-1, -1)]
+    );
+}
+
+#[test]
+fn test_matches_scan_on_random_boards() {
+    let mut rng = Rng::new(11);
+    let mut pieces = PieceGenerator::new(PieceGeneratorConfig::default(), 5);
+
+    for _ in 0..100 {
+        let width = rng.range(1, 30);
+        let height = rng.range(1, 20);
+        let board = random_board(&mut rng, width, height, 0.45);
+        let piece = pieces.next_piece();
+
+        for side in [Side::Me, Side::Opponent] {
+            assert_eq!(
+                valid_placements(&board, &piece, side),
+                scan_valid_placements(&board, &piece, side)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_wide_boards_fall_back_to_scan() {
+    let mut rng = Rng::new(3);
+    let board = random_board(&mut rng, 140, 6, 0.45);
+    let piece = Piece::new(2, 2, vec![vec!['O', 'O'], vec!['.', 'O']]);
+
+    assert!(Bitboard::from_board(&board).is_none());
+    assert_eq!(
+        valid_placements(&board, &piece, Side::Me),
+        scan_valid_placements(&board, &piece, Side::Me)
+    );
+}
+
+#[test]
+fn test_widest_supported_board() {
+    let mut rng = Rng::new(4);
+    let board = random_board(&mut rng, 128, 5, 0.45);
+    let piece = Piece::new(3, 1, vec![vec!['O', '.', 'O']]);
+    let bitboard = Bitboard::from_board(&board).unwrap();
+
+    assert_eq!(
+        bitboard.valid_placements(&CompiledPiece::new(&piece).unwrap(), Side::Me),
+        scan_valid_placements(&board, &piece, Side::Me)
+    );
+}
+
+#[test]
+fn test_placed_pieces_keep_bitboard_in_step() {
+    let mut rng = Rng::new(8);
+    let mut pieces = PieceGenerator::new(PieceGeneratorConfig::default(), 2);
+
+    for _ in 0..30 {
+        let width = rng.range(4, 30);
+        let height = rng.range(4, 20);
+        let mut position = Position::new(random_board(&mut rng, width, height, 0.45));
+
+        for ply in 0..6 {
+            let side = if ply % 2 == 0 {
+                Side::Me
+            } else {
+                Side::Opponent
+            };
+            let piece = pieces.next_piece();
+            let compiled = CompiledPiece::new(&piece);
+            let moves = position.valid_placements(&piece, compiled.as_ref(), side);
+            let Some(&(x, y)) = moves.get(rng.range(0, moves.len().max(1) - 1)) else {
+                continue;
+            };
+
+            position.place(&piece, compiled.as_ref(), x, y, side);
+            assert_eq!(position.bitboard, Bitboard::from_board(&position.board));
+        }
+    }
+}
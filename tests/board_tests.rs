@@ -0,0 +1,89 @@
+mod common;
+
+use common::board;
+use filler::board::{Board, Cell};
+use filler::player::Player;
+
+#[test]
+fn test_parse_cells() {
+    let board = board(&["@a.", "$s."]);
+
+    assert_eq!((board.width, board.height), (3, 2));
+    assert_eq!(board.cell(0, 0), Cell::Mine);
+    assert_eq!(board.cell(1, 0), Cell::MineLast);
+    assert_eq!(board.cell(0, 1), Cell::Theirs);
+    assert_eq!(board.cell(1, 1), Cell::TheirsLast);
+    assert_eq!(board.cell(2, 1), Cell::Empty);
+    assert!(board.cell(1, 0).is_mine() && board.cell(1, 1).is_theirs());
+}
+
+#[test]
+fn test_round_trip_to_chars() {
+    let grid = vec![vec!['@', 'a', '.'], vec!['$', 's', '.']];
+    let (me, opponent) = (Player::new(2), Player::new(1));
+    let board = Board::from_chars(&grid, &me, &opponent).unwrap();
+
+    assert_eq!(board.cell(0, 1), Cell::Mine);
+    assert_eq!(board.to_chars(&me, &opponent), grid);
+}
+
+#[test]
+fn test_rejects_unknown_cells() {
+    let (me, opponent) = (Player::new(1), Player::new(2));
+
+    assert_eq!(Cell::from_char('x', &me, &opponent), None);
+    assert_eq!(Cell::from_char('O', &me, &opponent), None);
+    assert_eq!(
+        Board::from_chars(&[vec!['@', '.'], vec!['.', '?']], &me, &opponent),
+        Err("unknown cell '?' at (1, 1)".to_string())
+    );
+    assert!(Board::from_chars(&[vec!['@', '.'], vec!['.']], &me, &opponent).is_err());
+}
+
+#[test]
+fn test_bounds_checked_access() {
+    let board = board(&["@.", ".$"]);
+
+    assert_eq!(board.get(0, 0), Some(Cell::Mine));
+    assert_eq!(board.get(-1, 0), None);
+    assert_eq!(board.get(0, 2), None);
+    assert_eq!(board.get(2, 1), None);
+    assert!(board.in_bounds(1, 1) && !board.in_bounds(1, -1));
+}
+
+#[test]
+fn test_neighbours() {
+    let board = board(&["@..", ".$.", "..."]);
+
+    assert_eq!(board.neighbours(1, 1).count(), 8);
+    let corner: Vec<_> = board.neighbours(0, 0).collect();
+    assert_eq!(
+        corner,
+        vec![
+            (1, 0, Cell::Empty),
+            (0, 1, Cell::Empty),
+            (1, 1, Cell::Theirs)
+        ]
+    );
+
+    let orthogonal: Vec<_> = board.orthogonal_neighbours(1, 0).collect();
+    assert_eq!(
+        orthogonal,
+        vec![
+            (0, 0, Cell::Mine),
+            (2, 0, Cell::Empty),
+            (1, 1, Cell::Theirs)
+        ]
+    );
+}
+
+#[test]
+fn test_set_and_iter() {
+    let mut board = Board::new(2, 2);
+    board.set(1, 0, Cell::MineLast);
+
+    let cells: Vec<_> = board.iter().collect();
+    assert_eq!(cells[1], (1, 0, Cell::MineLast));
+    assert_eq!(cells.len(), 4);
+    assert!(cells.iter().filter(|(_, _, cell)| cell.is_empty()).count() == 3);
+}
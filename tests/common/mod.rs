@@ -0,0 +1,67 @@
+// Fixtures shared by the integration tests; each test file uses only some of them
+#![allow(dead_code)]
+
+use filler::board::{Board, Cell};
+use filler::filler_ai::FillerAi;
+use filler::piece::Piece;
+use filler::player::Player;
+use filler::rng::Rng;
+
+// Rows of engine symbols as a grid
+pub fn grid(rows: &[&str]) -> Vec<Vec<char>> {
+    rows.iter().map(|row| row.chars().collect()).collect()
+}
+
+// Rows of engine symbols as a board seen by player 1
+pub fn board(rows: &[&str]) -> Board {
+    board_as(rows, 1)
+}
+
+// Rows of engine symbols as a board seen by the given player
+pub fn board_as(rows: &[&str], player: u8) -> Board {
+    let opponent = if player == 1 { 2 } else { 1 };
+    Board::from_chars(&grid(rows), &Player::new(player), &Player::new(opponent)).unwrap()
+}
+
+// A board where each cell is taken with probability `density`, by either player
+// and as plain territory or last-placed alike
+pub fn random_board(rng: &mut Rng, width: usize, height: usize, density: f64) -> Board {
+    let mut board = Board::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            if rng.next_f64() < density {
+                let cell = match rng.range(0, 3) {
+                    0 => Cell::Mine,
+                    1 => Cell::MineLast,
+                    2 => Cell::Theirs,
+                    _ => Cell::TheirsLast,
+                };
+                board.set(x, y, cell);
+            }
+        }
+    }
+    board
+}
+
+// Command line arguments for Options::parse
+pub fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|arg| arg.to_string()).collect()
+}
+
+// Our AI (player 1) on the given grid, holding `piece`
+pub fn setup(board: Vec<Vec<char>>, piece: Piece) -> FillerAi {
+    let mut ai = FillerAi::new(1);
+    let (width, height) = (board[0].len(), board.len());
+    ai.update_board(width, height, board).unwrap();
+    ai.update_piece(piece);
+    ai
+}
+
+// An empty grid with our cell near the top-left corner and theirs near the
+// bottom-right one
+pub fn open_board(width: usize, height: usize) -> Vec<Vec<char>> {
+    let mut board = vec![vec!['.'; width]; height];
+    board[1][1] = '@';
+    board[height - 2][width - 2] = '$';
+    board
+}
@@ -0,0 +1,116 @@
+use filler::config::ScoringConfig;
+use filler::filler_ai::FillerAi;
+use filler::options::Options;
+use filler::piece::Piece;
+
+#[test]
+fn test_defaults_match_original_weights() {
+    let config = ScoringConfig::default();
+
+    assert_eq!(config.heat_weight, 100);
+    assert_eq!(config.blocking_weight, 20);
+    assert_eq!(config.expansion_weight, 5);
+    assert_eq!(config.efficiency_weight, 10);
+    assert_eq!(config.territory_weight, 150);
+    assert_eq!(config.anticipation_weight, 100);
+    assert_eq!(config.proximity_bonus, 5);
+    assert_eq!(config.efficiency_multiplier, 2);
+}
+
+#[test]
+fn test_parse_toml() {
+    let config = ScoringConfig::parse(
+        "# tuned for map02\n[scoring]\nheat_weight = 80\nproximity_bonus = 7 # was 5\n",
+    )
+    .unwrap();
+
+    assert_eq!(config.heat_weight, 80);
+    assert_eq!(config.proximity_bonus, 7);
+    assert_eq!(config.blocking_weight, 20);
+}
+
+#[test]
+fn test_parse_json() {
+    let config =
+        ScoringConfig::parse("{ \"expansion_weight\": 9, \"efficiency_multiplier\": 3 }").unwrap();
+
+    assert_eq!(config.expansion_weight, 9);
+    assert_eq!(config.efficiency_multiplier, 3);
+    assert_eq!(config.heat_weight, 100);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(ScoringConfig::parse("heat_weight = high").is_err());
+    assert!(ScoringConfig::parse("speed = 3").is_err());
+    assert!(ScoringConfig::parse("heat_weight 3").is_err());
+    assert!(ScoringConfig::parse("{ \"heat_weight\": 3").is_err());
+}
+
+#[test]
+fn test_environment_overrides() {
+    let mut config = ScoringConfig::default();
+    config
+        .apply_env(|key| (key == "FILLER_BLOCKING_WEIGHT").then(|| "42".to_string()))
+        .unwrap();
+    assert_eq!(config.blocking_weight, 42);
+
+    assert!(
+        config
+            .apply_env(|key| (key == "FILLER_HEAT_WEIGHT").then(|| "x".to_string()))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_options_load_config_file() {
+    let path = std::env::temp_dir().join("filler_config_test.toml");
+    std::fs::write(&path, "heat_weight = 1\nblocking_weight = 2\n").unwrap();
+    let path = path.to_str().unwrap().to_string();
+
+    let env = |key: &str| (key == "FILLER_BLOCKING_WEIGHT").then(|| "3".to_string());
+    let options = Options::parse(vec!["--config".to_string(), path], env).unwrap();
+
+    assert_eq!(options.scoring.heat_weight, 1);
+    assert_eq!(options.scoring.blocking_weight, 3);
+    assert!(
+        Options::parse(
+            vec!["--config".to_string(), "/missing.toml".to_string()],
+            |_| None
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_weights_change_scores() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(
+        4,
+        2,
+        vec![vec!['@', '.', 's', '.'], vec!['.', '.', '.', '.']],
+    )
+    .unwrap();
+    ai.update_piece(Piece::new(1, 1, vec![vec!['O']]));
+    let default_blocking = ai.calculate_blocking_score(2, 1);
+    let default_efficiency = ai.calculate_piece_efficiency(2, 1);
+
+    ai.scoring.proximity_bonus = 10;
+    ai.scoring.efficiency_multiplier = 6;
+    assert_eq!(ai.calculate_blocking_score(2, 1), default_blocking * 2);
+    assert_eq!(ai.calculate_piece_efficiency(2, 1), default_efficiency * 3);
+
+    ai.scoring = ScoringConfig {
+        heat_weight: 0,
+        blocking_weight: 0,
+        expansion_weight: 0,
+        efficiency_weight: 1,
+        territory_weight: 0,
+        anticipation_weight: 0,
+        ..ScoringConfig::default()
+    };
+    assert_eq!(
+        ai.score_placement(2, 1),
+        ai.calculate_piece_efficiency(2, 1)
+    );
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{args, open_board, setup};
+use filler::deadline::Deadline;
+use filler::filler_ai::FillerAi;
+use filler::mcts::MonteCarloSearch;
+use filler::options::{DEFAULT_MOVE_TIME_MS, Options};
+use filler::piece::Piece;
+use filler::search::AlphaBetaSearch;
+
+fn open_ai() -> FillerAi {
+    setup(
+        open_board(12, 10),
+        Piece::new(2, 2, vec![vec!['O', 'O'], vec!['.', 'O']]),
+    )
+}
+
+#[test]
+fn test_deadline_expiry() {
+    assert!(!Deadline::never().expired());
+    assert_eq!(Deadline::never().remaining(), None);
+    assert!(Deadline::after(Duration::ZERO).expired());
+
+    let later = Deadline::after(Duration::from_secs(60));
+    assert!(!later.expired());
+    assert!(later.remaining().unwrap() > Duration::from_secs(59));
+}
+
+#[test]
+fn test_min_picks_earlier_deadline() {
+    let soon = Deadline::after(Duration::ZERO);
+    let later = Deadline::after(Duration::from_secs(60));
+
+    assert_eq!(soon.min(later), soon);
+    assert_eq!(later.min(soon), soon);
+    assert_eq!(Deadline::never().min(later), later);
+    assert_eq!(later.min(Deadline::never()), later);
+}
+
+#[test]
+fn test_move_time_option() {
+    let default = Options::parse(args(&[]), |_| None).unwrap();
+    assert_eq!(
+        default.move_time,
+        Duration::from_millis(DEFAULT_MOVE_TIME_MS)
+    );
+
+    let env = |key: &str| (key == "FILLER_MOVE_TIME_MS").then(|| "750".to_string());
+    assert_eq!(
+        Options::parse(args(&[]), env).unwrap().move_time,
+        Duration::from_millis(750)
+    );
+    assert_eq!(
+        Options::parse(args(&["--time", "300"]), env)
+            .unwrap()
+            .move_time,
+        Duration::from_millis(300)
+    );
+
+    assert!(Options::parse(args(&["--time", "0"]), |_| None).is_err());
+    assert!(Options::parse(args(&["--time", "soon"]), |_| None).is_err());
+    assert!(Options::parse(args(&["--time"]), |_| None).is_err());
+}
+
+#[test]
+fn test_expired_deadline_still_returns_a_legal_move() {
+    let mut ai = open_ai();
+    let legal = ai.find_all_valid_placements();
+    ai.deadline = Deadline::after(Duration::ZERO);
+
+    for chosen in [
+        ai.find_best_move(),
+        AlphaBetaSearch::default().find_move(&ai),
+        MonteCarloSearch::default().find_move(&ai),
+    ] {
+        assert!(legal.contains(&chosen.unwrap()));
+    }
+}
+
+#[test]
+fn test_mcts_without_playouts_plays_the_heuristic_move() {
+    // The move deadline stays open, so the root is fully ordered by score, but the
+    // search's own budget leaves no time for a single playout
+    let ai = open_ai();
+    let search = MonteCarloSearch {
+        time_budget: Duration::ZERO,
+        ..MonteCarloSearch::default()
+    };
+    let best = ai.find_best_move().unwrap();
+    assert_ne!(best, ai.find_all_valid_placements()[0]);
+
+    assert!(search.run(&ai).iter().all(|stats| stats.visits == 0));
+    assert_eq!(search.find_move(&ai), Some(best));
+}
+
+#[test]
+fn test_search_stops_at_move_deadline() {
+    let mut ai = open_ai();
+    let search = AlphaBetaSearch {
+        max_depth: 50,
+        time_budget: Duration::from_secs(60),
+        ..AlphaBetaSearch::default()
+    };
+
+    ai.deadline = Deadline::after(Duration::from_millis(50));
+    let started = std::time::Instant::now();
+    assert!(search.find_move(&ai).is_some());
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,76 @@
+use filler::distance::{DistanceField, orthogonal_neighbours};
+
+fn field(rows: &[&str]) -> DistanceField {
+    let grid: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+    DistanceField::compute(
+        grid[0].len(),
+        grid.len(),
+        |x, y| grid[y][x] == 'S',
+        |x, y| grid[y][x] == '.',
+    )
+}
+
+#[test]
+fn test_open_grid_matches_manhattan() {
+    let distances = field(&["S....", ".....", "....."]);
+
+    for y in 0..3 {
+        for x in 0..5 {
+            assert_eq!(distances.get(x, y), Some((x + y) as u32));
+        }
+    }
+}
+
+#[test]
+fn test_walls_lengthen_paths() {
+    let distances = field(&["S#...", ".#.#.", "...#."]);
+
+    // Straight-line distance to (2, 0) is 2, the path around the wall is 6
+    assert_eq!(distances.get(2, 0), Some(6));
+    assert_eq!(distances.get(4, 2), Some(10));
+}
+
+#[test]
+fn test_sealed_cells_are_unreachable() {
+    let distances = field(&["S.#..", "..#..", "###.."]);
+
+    assert_eq!(distances.get(1, 1), Some(2));
+    assert_eq!(distances.get(3, 0), None);
+    assert_eq!(distances.get(2, 0), None);
+    assert_eq!(distances.get(9, 9), None);
+}
+
+#[test]
+fn test_multiple_sources() {
+    let distances = field(&["S...S"]);
+
+    assert_eq!(distances.get(1, 0), Some(1));
+    assert_eq!(distances.get(2, 0), Some(2));
+    assert_eq!(distances.get(3, 0), Some(1));
+}
+
+#[test]
+fn test_neighbours_stay_in_bounds() {
+    let corner: Vec<_> = orthogonal_neighbours(0, 0, 3, 3).collect();
+    assert_eq!(corner, vec![(1, 0), (0, 1)]);
+    assert_eq!(orthogonal_neighbours(1, 1, 3, 3).count(), 4);
+}
+
+#[test]
+fn test_close_and_add_sources_match_recompute() {
+    let mut distances = field(&["S....", ".....", "....."]);
+
+    let changed = distances.close(
+        &[(1, 0), (1, 1)],
+        |x, y| (x, y) == (0, 0),
+        |x, y| !matches!((x, y), (0, 0) | (1, 0) | (1, 1)),
+    );
+    assert_eq!(distances, field(&["S#...", ".#...", "....."]));
+    assert!(changed.contains(&(2, 0)));
+
+    distances.add_sources(&[(4, 0)], |x, y| {
+        !matches!((x, y), (0, 0) | (1, 0) | (1, 1) | (4, 0))
+    });
+    let grid = ["S#..S", ".#...", "....."];
+    assert_eq!(distances, field(&grid));
+}
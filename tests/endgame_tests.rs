@@ -0,0 +1,138 @@
+mod common;
+
+use common::{board, grid, open_board, random_board};
+use filler::endgame::{Packer, Separation};
+use filler::filler_ai::FillerAi;
+use filler::mcts::MonteCarloSearch;
+use filler::game_state::GameState;
+use filler::piece::Piece;
+use filler::regions::RegionAnalysis;
+use filler::rng::Rng;
+use filler::search::AlphaBetaSearch;
+
+// Each player walled into their own half, with no gap between the walls
+const WALLED: [&str; 3] = ["..@$..", "..@$..", "..@$.."];
+
+// A five cell corridor walled in by our territory, the opponent off to the side
+const CORRIDOR: [&str; 3] = ["@@@@@@@$", "@.....@$", "@@@@@@@$"];
+
+fn corridor_ai() -> FillerAi {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(8, 3, grid(&CORRIDOR)).unwrap();
+    ai.update_piece(Piece::new(1, 2, vec![vec!['O'], vec!['O']]));
+    ai
+}
+
+#[test]
+fn test_separation_counts() {
+    let walled = Separation::compute(&board(&WALLED), 1);
+    assert_eq!(
+        walled,
+        Separation {
+            mine: 6,
+            theirs: 6,
+            shared: 0,
+            unreachable: 0,
+        }
+    );
+    assert!(walled.is_endgame());
+
+    let open = Separation::compute(&board(&["..@.$..", "..@.$.."]), 1);
+    assert_eq!((open.mine, open.shared, open.theirs), (4, 2, 4));
+    assert!(!open.is_endgame());
+
+    let empty = Separation::compute(&board(&["...", "..."]), 1);
+    assert_eq!(empty.unreachable, 6);
+}
+
+#[test]
+fn test_diagonal_gap_keeps_areas_shared() {
+    // The players' walls meet only at a corner, so a piece can still slip through
+    let separation = Separation::compute(&board(&["@...", ".@..", "..$.", "...$"]), 1);
+
+    assert_eq!(separation.shared, 12);
+    assert!(!separation.is_endgame());
+}
+
+#[test]
+fn test_gapped_pieces_reach_over_walls() {
+    // O.O can cover a cell two columns past the one it anchors on
+    let gapped = Piece::new(3, 1, vec![vec!['O', '.', 'O']]);
+    assert_eq!(gapped.reach(), 2);
+
+    let over = Separation::compute(&board(&WALLED), gapped.reach());
+    assert_eq!((over.shared, over.mine, over.theirs), (12, 0, 0));
+    assert!(!over.is_endgame());
+
+    let mut ai = corridor_ai();
+    ai.update_piece(gapped);
+    assert!(!ai.is_endgame());
+}
+
+#[test]
+fn test_reach_one_matches_regions() {
+    let mut rng = Rng::new(5);
+    for _ in 0..30 {
+        let (width, height) = (rng.range(2, 12), rng.range(2, 10));
+        let cells = random_board(&mut rng, width, height, 0.4);
+        let regions = RegionAnalysis::compute(&cells);
+
+        assert_eq!(
+            Separation::within_reach(&cells, 1),
+            Separation::from_regions(&cells, &regions, 1)
+        );
+    }
+}
+
+#[test]
+fn test_packer_avoids_stranding_cells() {
+    let corridor = board(&CORRIDOR);
+    let frontier: Vec<(usize, usize)> = GameState::new(&corridor).my_frontier().collect();
+    let piece = Piece::new(1, 2, vec![vec!['O'], vec!['O']]);
+    let packer = Packer {
+        pieces: vec![Piece::new(3, 1, vec![vec!['O', 'O', 'O']])],
+    };
+    let score = |x| packer.packing_score(&corridor, &frontier, &piece, x, 0);
+
+    // Filling x=2 leaves x=1 where no three-wide piece can reach it
+    assert!(score(1) > score(2));
+    // Against the corridor end the piece touches one more wall than in the middle
+    assert_eq!(score(1), score(3) + 1);
+    assert_eq!(score(1), score(5));
+
+    let moves: Vec<(i32, i32)> = (1..6).map(|x| (x, 0)).collect();
+    assert_eq!(
+        packer.best_move(&corridor, &frontier, &piece, &moves, Default::default()),
+        Some((1, 0))
+    );
+}
+
+#[test]
+fn test_ai_packs_in_the_endgame() {
+    let ai = corridor_ai();
+
+    assert!(ai.is_endgame());
+    assert_eq!(ai.find_best_move(), Some((1, 0)));
+}
+
+#[test]
+fn test_searches_defer_to_the_packer() {
+    let ai = corridor_ai();
+    let expected = ai.find_best_move();
+
+    assert_eq!(AlphaBetaSearch::default().find_move(&ai), expected);
+    assert_eq!(MonteCarloSearch::default().find_move(&ai), expected);
+}
+
+#[test]
+fn test_no_endgame_before_the_first_board() {
+    assert!(!FillerAi::new(1).is_endgame());
+}
+
+#[test]
+fn test_open_board_is_not_endgame() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(12, 10, open_board(12, 10)).unwrap();
+
+    assert!(!ai.is_endgame());
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "export")]
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use filler::board::{Board, Cell};
+use filler::export::{GifWriter, Image, render_board, render_svg, write_png};
+use filler::palette::{
+    BORDER_COLOR, EMPTY_COLOR, PLACED_OUTLINE, PLAYER1_RECENT, PLAYER1_TERRITORY,
+    PLAYER2_TERRITORY, Rgb,
+};
+
+fn board() -> Board {
+    let mut board = Board::new(3, 2);
+    board.set(0, 0, Cell::Mine);
+    board.set(1, 0, Cell::MineLast);
+    board.set(2, 1, Cell::Theirs);
+    board
+}
+
+// Header and pixels of a PNG, expanded to 8-bit RGB
+fn decode_png(png: &[u8]) -> (png::Info<'static>, Vec<u8>) {
+    let mut decoder = png::Decoder::new(Cursor::new(png));
+    decoder.set_transformations(png::Transformations::EXPAND);
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    let size = reader.next_frame(&mut pixels).unwrap().buffer_size();
+    pixels.truncate(size);
+    (reader.info().clone(), pixels)
+}
+
+fn rgb(image: &Image) -> Vec<u8> {
+    (0..image.height)
+        .flat_map(|y| (0..image.width).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let pixel = image.pixel(x, y);
+            [pixel.r, pixel.g, pixel.b]
+        })
+        .collect()
+}
+
+// (left, top, width, height) and delay of each frame
+fn gif_frames(gif: &[u8]) -> Vec<([u16; 4], u16)> {
+    let mut decoder = gif::DecodeOptions::new().read_info(gif).unwrap();
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        frames.push((
+            [frame.left, frame.top, frame.width, frame.height],
+            frame.delay,
+        ));
+    }
+    frames
+}
+
+#[test]
+fn test_render_board_pixels() {
+    let image = render_board(&board(), 1, 6, &[(1, 0)]);
+
+    assert_eq!((image.width, image.height), (18, 12));
+    // Border, then the fill inside it
+    assert_eq!(image.pixel(0, 0), BORDER_COLOR);
+    assert_eq!(image.pixel(3, 3), PLAYER1_TERRITORY);
+    assert_eq!(image.pixel(15, 9), PLAYER2_TERRITORY);
+    assert_eq!(image.pixel(3, 9), EMPTY_COLOR);
+    // The highlighted cell is outlined two pixels deep, its centre keeps the fill
+    assert_eq!(image.pixel(6, 0), PLACED_OUTLINE);
+    assert_eq!(image.pixel(7, 1), PLACED_OUTLINE);
+    assert_eq!(image.pixel(9, 3), PLAYER1_RECENT);
+
+    // Playing as player 2 swaps the colours, not the cells
+    let flipped = render_board(&board(), 2, 6, &[]);
+    assert_eq!(flipped.pixel(3, 3), PLAYER2_TERRITORY);
+    assert_eq!(flipped.pixel(15, 9), PLAYER1_TERRITORY);
+}
+
+#[test]
+fn test_png_with_palette() {
+    let image = render_board(&board(), 1, 10, &[]);
+    let mut png = Vec::new();
+    write_png(&image, &mut png).unwrap();
+
+    let (info, pixels) = decode_png(&png);
+    assert_eq!((info.width, info.height), (30, 20));
+    assert_eq!(info.color_type, png::ColorType::Indexed);
+    assert_eq!(info.bit_depth, png::BitDepth::Eight);
+    // Border, empty, both territories and the recent red; cells cover the background
+    assert_eq!(
+        info.palette.as_ref().map(|palette| palette.len()),
+        Some(3 * 5)
+    );
+    assert_eq!(pixels, rgb(&image));
+    // Runs and repeated rows keep the whole file well below the 630 raw bytes
+    assert!(png.len() < 200);
+}
+
+#[test]
+fn test_png_without_palette() {
+    // More colours than a palette holds falls back to 8-bit RGB
+    let mut image = Image::new(300, 1, EMPTY_COLOR);
+    for x in 0..300 {
+        image.set(x, 0, Rgb::new(x as u8, (x / 256) as u8, 0));
+    }
+    let mut png = Vec::new();
+    write_png(&image, &mut png).unwrap();
+
+    let (info, pixels) = decode_png(&png);
+    assert_eq!(info.color_type, png::ColorType::Rgb);
+    assert!(info.palette.is_none());
+    assert_eq!(pixels, rgb(&image));
+}
+
+#[test]
+fn test_svg() {
+    let svg = render_svg(&board(), 1, 10, &[(2, 1)]);
+
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\"")
+    );
+    assert!(svg.trim_end().ends_with("</svg>"));
+    // Background, six cells and one outline
+    assert_eq!(svg.matches("<rect").count(), 8);
+    assert!(svg.contains("<rect x=\"0.5\" y=\"0.5\" width=\"9\" height=\"9\" fill=\"#ff6464\""));
+    assert!(svg.contains("fill=\"#6464ff\""));
+    assert!(svg.contains(
+        "<rect x=\"21\" y=\"11\" width=\"8\" height=\"8\" fill=\"none\" stroke=\"#ffd700\""
+    ));
+}
+
+#[test]
+fn test_gif_frames() {
+    let first = render_board(&board(), 1, 5, &[]);
+    let mut next = board();
+    next.set(1, 1, Cell::TheirsLast);
+    let second = render_board(&next, 1, 5, &[]);
+
+    let mut gif = GifWriter::new(Vec::new(), 15, 10, Duration::from_millis(200)).unwrap();
+    for frame in [&first, &second, &second] {
+        gif.add_frame(frame).unwrap();
+    }
+    let gif = gif.finish().unwrap();
+
+    assert_eq!(&gif[..6], b"GIF89a");
+    assert_eq!(&gif[6..10], &[15, 0, 10, 0]);
+    assert_eq!(gif.last(), Some(&0x3B));
+    // Full frame, then only the fill of the changed cell, then a one-pixel repeat,
+    // each shown for 20 hundredths of a second
+    assert_eq!(
+        gif_frames(&gif),
+        vec![([0, 0, 15, 10], 20), ([6, 6, 3, 3], 20), ([0, 0, 1, 1], 20)]
+    );
+}
+
+#[test]
+fn test_gif_rejects_bad_frames() {
+    let mut gif = GifWriter::new(Vec::new(), 4, 4, Duration::ZERO).unwrap();
+    assert!(gif.add_frame(&Image::new(5, 4, EMPTY_COLOR)).is_err());
+    assert!(gif.add_frame(&Image::new(4, 4, Rgb::new(1, 2, 3))).is_err());
+    assert!(gif.add_frame(&Image::new(4, 4, EMPTY_COLOR)).is_ok());
+
+    assert!(GifWriter::new(Vec::new(), 0, 4, Duration::ZERO).is_err());
+    assert!(GifWriter::new(Vec::new(), 70_000, 4, Duration::ZERO).is_err());
+}
@@ -1,5 +1,11 @@
+mod common;
+
+use common::{grid, open_board, setup};
+use filler::board::{Cell, Side};
+use filler::config::ScoringConfig;
 use filler::filler_ai::FillerAi;
 use filler::piece::Piece;
+use filler::rules::valid_placements;
 
 #[test]
 fn test_ai_initialization() {
@@ -20,11 +26,43 @@ fn test_board_update() {
         vec!['@', '.', '.', '$'],
     ];
 
-    ai.update_board(4, 3, board.clone());
+    ai.update_board(4, 3, board.clone()).unwrap();
 
     assert_eq!(ai.board_width, 4);
     assert_eq!(ai.board_height, 3);
-    assert_eq!(ai.board, board);
+    assert_eq!(ai.cells.to_chars(&ai.my_player, &ai.opponent_player), board);
+    assert_eq!(ai.cells.cell(1, 0), Cell::Mine);
+    assert_eq!(ai.cells.cell(3, 2), Cell::Theirs);
+    assert_eq!(ai.cells.cell(0, 1), Cell::Empty);
+}
+
+#[test]
+fn test_board_update_rejects_bad_boards() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(2, 1, vec![vec!['@', '$']]).unwrap();
+
+    assert!(ai.update_board(2, 1, vec![vec!['@', 'x']]).is_err());
+    assert!(ai.update_board(3, 1, vec![vec!['@', '.']]).is_err());
+    assert!(
+        ai.update_board(2, 2, vec![vec!['@', '.'], vec!['.']])
+            .is_err()
+    );
+    // The last good board is kept
+    assert_eq!((ai.board_width, ai.board_height), (2, 1));
+    assert_eq!(ai.cells.cell(1, 0), Cell::Theirs);
+}
+
+#[test]
+fn test_cells_follow_player_side() {
+    let mut ai = FillerAi::new(2);
+    let board = vec![vec!['@', 'a', '$', 's']];
+
+    ai.update_board(4, 1, board).unwrap();
+
+    assert_eq!(ai.cells.cell(0, 0), Cell::Theirs);
+    assert_eq!(ai.cells.cell(1, 0), Cell::TheirsLast);
+    assert_eq!(ai.cells.cell(2, 0), Cell::Mine);
+    assert_eq!(ai.cells.cell(3, 0), Cell::MineLast);
 }
 
 #[test]
@@ -47,7 +85,7 @@ fn test_heat_map_generation() {
         vec!['.', '.', '.', '.'],
     ];
 
-    ai.update_board(4, 3, board);
+    ai.update_board(4, 3, board).unwrap();
 
     // Heat should be higher (closer to opponent) on the right side
     assert!(ai.heat_map[1][3] > ai.heat_map[1][0]);
@@ -62,7 +100,7 @@ fn test_valid_placement_detection() {
         vec!['.', '.', '.', '$'],
     ];
 
-    ai.update_board(4, 3, board);
+    ai.update_board(4, 3, board).unwrap();
 
     let piece = Piece::new(2, 1, vec![vec!['O', 'O']]);
     ai.update_piece(piece);
@@ -83,7 +121,7 @@ fn test_no_valid_moves_when_blocked() {
         vec!['$', '$', '$'],
     ];
 
-    ai.update_board(3, 3, board);
+    ai.update_board(3, 3, board).unwrap();
 
     let piece = Piece::new(2, 1, vec![vec!['O', 'O']]);
     ai.update_piece(piece);
@@ -97,7 +135,7 @@ fn test_heat_score_calculation() {
     let mut ai = FillerAi::new(1);
     let board = vec![vec!['@', '.', '.', '$'], vec!['.', '.', '.', '.']];
 
-    ai.update_board(4, 2, board);
+    ai.update_board(4, 2, board).unwrap();
 
     let piece = Piece::new(1, 1, vec![vec!['O']]);
     ai.update_piece(piece);
@@ -114,7 +152,7 @@ fn test_blocking_score_calculation() {
     let mut ai = FillerAi::new(1);
     let board = vec![vec!['@', '.', 's', '.'], vec!['.', '.', '.', '.']];
 
-    ai.update_board(4, 2, board);
+    ai.update_board(4, 2, board).unwrap();
 
     let piece = Piece::new(1, 1, vec![vec!['O']]);
     ai.update_piece(piece);
@@ -135,7 +173,7 @@ fn test_expansion_score_calculation() {
         vec!['.', '.', '.', '$'],
     ];
 
-    ai.update_board(4, 3, board);
+    ai.update_board(4, 3, board).unwrap();
 
     let piece = Piece::new(1, 1, vec![vec!['O']]);
     ai.update_piece(piece);
@@ -152,7 +190,7 @@ fn test_piece_efficiency_scoring() {
     let mut ai = FillerAi::new(1);
     let board = vec![vec!['@', '.', '.', '.'], vec!['.', '.', '.', '.']];
 
-    ai.update_board(4, 2, board);
+    ai.update_board(4, 2, board).unwrap();
 
     // Large piece should have higher efficiency score
     let large_piece = Piece::new(2, 2, vec![vec!['O', 'O'], vec!['O', 'O']]);
@@ -171,14 +209,50 @@ fn test_best_move_selection() {
     let mut ai = FillerAi::new(1);
     let board = vec![vec!['@', '.', '.', 's'], vec!['.', '.', '.', '.']];
 
-    ai.update_board(4, 2, board);
+    ai.update_board(4, 2, board).unwrap();
 
     let piece = Piece::new(1, 1, vec![vec!['O']]);
     ai.update_piece(piece);
 
     let best_move = ai.find_best_move();
     assert!(best_move.is_some());
+}
+
+#[test]
+fn test_territory_weight_changes_best_move() {
+    let mut ai = setup(
+        open_board(6, 5),
+        Piece::new(2, 2, vec![vec!['O', '.'], vec!['O', 'O']]),
+    );
+    ai.scoring.territory_weight = 0;
+    let unweighted = ai.find_best_move().unwrap();
+    ai.scoring.territory_weight = ScoringConfig::default().territory_weight;
+    let weighted = ai.find_best_move().unwrap();
+
+    // The weighted move wins more of the board than the heat map's pick
+    assert_ne!(weighted, unweighted);
+    assert!(
+        ai.calculate_territory_score(weighted.0, weighted.1)
+            > ai.calculate_territory_score(unweighted.0, unweighted.1)
+    );
+}
 
+#[test]
+fn test_anticipation_weight_changes_best_move() {
+    // The opponent climbs the right edge towards the top corner
+    let mut ai = FillerAi::new(1);
+    ai.update_board(5, 4, grid(&["...@.", ".....", ".....", "....$"]))
+        .unwrap();
+    ai.update_board(5, 4, grid(&["...@.", "....s", "....s", "....$"]))
+        .unwrap();
+    ai.update_piece(Piece::new(2, 1, vec![vec!['O', 'O']]));
+
+    ai.scoring.anticipation_weight = 0;
+    assert_eq!(ai.find_best_move(), Some((2, 0)));
+    // Weighted, we take the corner in its path first
+    ai.scoring.anticipation_weight = ScoringConfig::default().anticipation_weight;
+    assert_eq!(ai.find_best_move(), Some((3, 0)));
+    assert!(ai.calculate_anticipation_score(3, 0) > ai.calculate_anticipation_score(2, 0));
 }
 
 #[test]
@@ -190,7 +264,7 @@ fn test_no_moves_returns_none() {
         vec!['$', '$', '$'],
     ];
 
-    ai.update_board(3, 3, board);
+    ai.update_board(3, 3, board).unwrap();
 
     let piece = Piece::new(
         3,
@@ -206,3 +280,97 @@ fn test_no_moves_returns_none() {
     let best_move = ai.find_best_move();
     assert!(best_move.is_none());
 }
+
+#[test]
+fn test_negative_offset_placement() {
+    let mut ai = FillerAi::new(1);
+    let board = vec![
+        vec!['@', '.', '.', '.'],
+        vec!['.', '.', '.', '.'],
+        vec!['.', '.', '.', '$'],
+    ];
+
+    ai.update_board(4, 3, board).unwrap();
+
+    // Solid cells start after an empty leading row and column
+    let piece = Piece::new(3, 2, vec![vec!['.', '.', '.'], vec!['.', 'O', 'O']]);
+    ai.update_piece(piece);
+
+    let valid_moves = ai.find_all_valid_placements();
+
+    // Only reachable by hanging the empty row/column off the top-left edge
+    assert!(valid_moves.contains(&(-1, -1)));
+    assert!(!valid_moves.contains(&(-2, -1)));
+}
+
+#[test]
+fn test_scores_with_negative_offset() {
+    let mut ai = FillerAi::new(1);
+    let board = vec![vec!['@', '.', '.', '$'], vec!['.', '.', '.', '.']];
+
+    ai.update_board(4, 2, board).unwrap();
+
+    let piece = Piece::new(2, 2, vec![vec!['.', '.'], vec!['.', 'O']]);
+    ai.update_piece(piece);
+
+    // Solid cell lands on (0, 0), same as a single-cell piece at (0, 0)
+    assert_eq!(ai.calculate_piece_efficiency(-1, -1), 2);
+    assert!(ai.calculate_expansion_score(-1, -1) > 0);
+    assert_eq!(ai.find_best_move(), Some((-1, -1)));
+}
+
+#[test]
+fn test_heat_map_respects_walls() {
+    let mut ai = FillerAi::new(1);
+    let board = vec![
+        vec!['$', '@', '.', '.', '.', '.'],
+        vec!['.', '@', '.', '.', '.', '.'],
+        vec!['.', '@', '.', '.', '.', '.'],
+        vec!['.', '.', '.', '.', '.', '.'],
+    ];
+
+    ai.update_board(6, 4, board).unwrap();
+
+    // (2, 0) is two steps away in a straight line but eight around our wall
+    assert_eq!(ai.heat_map[0][2], ai.heat_map[1][0] - 7);
+    assert!(ai.heat_map[3][1] > ai.heat_map[0][2]);
+}
+
+#[test]
+fn test_sealed_cells_get_no_heat() {
+    let mut ai = FillerAi::new(1);
+    let board = vec![
+        vec!['$', '.', '@', '.'],
+        vec!['.', '.', '@', '.'],
+        vec!['@', '@', '@', '.'],
+    ];
+
+    ai.update_board(4, 3, board).unwrap();
+
+    assert!(ai.heat_map[1][1] > 0);
+    assert_eq!(ai.heat_map[0][3], 0);
+    assert_eq!(ai.heat_map[2][3], 0);
+}
+
+#[test]
+fn test_frontier_candidates_match_full_scan() {
+    let mut ai = FillerAi::new(1);
+    let board = vec![
+        vec!['@', '@', '@', '.', '.'],
+        vec!['@', '@', '@', '.', '.'],
+        vec!['@', '@', '@', '.', '$'],
+    ];
+    ai.update_board(5, 3, board).unwrap();
+    let pieces = [
+        Piece::new(2, 1, vec![vec!['O', 'O']]),
+        Piece::new(1, 1, vec![vec!['O']]),
+        // Lone cells can overlap the interior (1, 1), away from the frontier
+        Piece::new(3, 1, vec![vec!['O', '.', 'O']]),
+    ];
+
+    for piece in pieces {
+        ai.update_piece(piece);
+        let expected = valid_placements(&ai.cells, &ai.current_piece, Side::Me);
+        assert_eq!(ai.find_all_valid_placements(), expected);
+    }
+}
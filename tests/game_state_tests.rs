@@ -0,0 +1,113 @@
+mod common;
+
+use common::board;
+use filler::board::{Board, Cell, Side};
+use filler::filler_ai::FillerAi;
+use filler::game_state::{GameState, diff_boards};
+use filler::map::{MapGeneratorConfig, generate_map};
+use filler::referee::Referee;
+use filler::regions::RegionAnalysis;
+use filler::rng::Rng;
+use filler::rules::valid_placements;
+
+// Two region analyses describe the same areas, whatever their labels
+fn same_regions(a: &RegionAnalysis, b: &RegionAnalysis, board: &Board) -> bool {
+    a.region_count() == b.region_count()
+        && board.iter().all(|(x, y, _)| {
+            a.open_neighbours(x, y) == b.open_neighbours(x, y)
+                && match (a.label(x, y), b.label(x, y)) {
+                    (Some(la), Some(lb)) => a.regions[la] == b.regions[lb],
+                    (None, None) => true,
+                    _ => false,
+                }
+        })
+}
+
+fn assert_matches_rebuild(state: &GameState, board: &Board) {
+    let fresh = GameState::new(board);
+    assert_eq!(state.opponent_distance, fresh.opponent_distance);
+    assert_eq!(state.my_distance, fresh.my_distance);
+    assert!(state.my_frontier().eq(fresh.my_frontier()));
+    assert!(state.their_frontier().eq(fresh.their_frontier()));
+    assert!(same_regions(&state.regions, &fresh.regions, board));
+}
+
+#[test]
+fn test_diff_boards() {
+    let before = board(&["@a..", "...$"]);
+    let after = board(&["@@..", "..s$"]);
+    let changes = diff_boards(&before, &after).unwrap();
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!((changes[0].x, changes[0].y), (1, 0));
+    assert!(changes[0].is_relabel());
+    assert_eq!(changes[1].after, Cell::TheirsLast);
+    assert!(changes[1].is_fill());
+    assert!(diff_boards(&before, &board(&["@a."])).is_none());
+}
+
+#[test]
+fn test_frontier_tracks_owned_border() {
+    let state = GameState::new(&board(&["@@.", "@@.", "..$"]));
+
+    assert_eq!(
+        state.my_frontier().collect::<Vec<_>>(),
+        vec![(1, 0), (0, 1), (1, 1)]
+    );
+    assert_eq!(state.their_frontier().collect::<Vec<_>>(), vec![(2, 2)]);
+}
+
+#[test]
+fn test_resize_rebuilds() {
+    let small = board(&["@.", ".$"]);
+    let large = board(&["@..", "...", "..$"]);
+    let mut state = GameState::new(&small);
+
+    assert_eq!(state.update(&small, &large), None);
+    assert_matches_rebuild(&state, &large);
+}
+
+#[test]
+fn test_incremental_updates_match_rebuild() {
+    let mut rng = Rng::new(21);
+
+    for seed in 0..4 {
+        let map = generate_map(
+            &MapGeneratorConfig {
+                min_width: 12,
+                max_width: 30,
+                min_height: 10,
+                max_height: 20,
+                ..MapGeneratorConfig::default()
+            },
+            seed,
+        );
+        let mut referee = Referee::new(&map, seed);
+        let mut ai = FillerAi::new(1);
+
+        while !referee.is_over() {
+            for index in 0..2 {
+                if !referee.active[index] {
+                    continue;
+                }
+                let piece = referee.next_piece();
+                let side = if index == 0 { Side::Me } else { Side::Opponent };
+                let moves = valid_placements(&referee.board, &piece, side);
+                let reply = (!moves.is_empty()).then(|| moves[rng.range(0, moves.len() - 1)]);
+                referee.play_move(index, &piece, reply);
+            }
+
+            ai.update_board(referee.board_width, referee.board_height, referee.grid())
+                .unwrap();
+            assert_matches_rebuild(&ai.state, &ai.cells);
+
+            let mut fresh = FillerAi::new(1);
+            fresh
+                .update_board(referee.board_width, referee.board_height, referee.grid())
+                .unwrap();
+            assert_eq!(ai.heat_map, fresh.heat_map);
+            // The bitboard is patched from the turn's changes, never repacked
+            assert_eq!(ai.bitboard, fresh.bitboard);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use filler::filler_ai::FillerAi;
+use filler::map::{Map, MapGeneratorConfig, STANDARD_MAPS, generate_map};
+
+#[test]
+fn test_parse_directive_format() {
+    let map = Map::parse("# demo\nsize 6 4\n@ 1 1\n$ 4 2 # opponent\n").unwrap();
+
+    assert_eq!(map.width, 6);
+    assert_eq!(map.height, 4);
+    assert_eq!(map.starts, [(1, 1), (4, 2)]);
+}
+
+#[test]
+fn test_parse_grid_format() {
+    let map = Map::parse("....\n.@..\n...$\n").unwrap();
+
+    assert_eq!(map.width, 4);
+    assert_eq!(map.height, 3);
+    assert_eq!(map.starts, [(1, 1), (3, 2)]);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(Map::parse("").is_err());
+    assert!(Map::parse("size 5 5\n@ 1 1\n").is_err());
+    assert!(Map::parse("size 5 5\n@ 1 1\n$ 5 0\n").is_err());
+    assert!(Map::parse("size 5 5\n@ 1 1\n$ 1 1\n").is_err());
+    assert!(Map::parse("size 5\n@ 1 1\n$ 2 2\n").is_err());
+    assert!(Map::parse("..@\n.$\n").is_err());
+    assert!(Map::parse("@@.\n..$\n").is_err());
+}
+
+#[test]
+fn test_to_board_feeds_ai() {
+    let map = Map::parse("size 5 3\n@ 0 0\n$ 4 2\n").unwrap();
+    let board = map.to_board();
+
+    assert_eq!(board.len(), 3);
+    assert_eq!(board[0], vec!['@', '.', '.', '.', '.']);
+    assert_eq!(board[2][4], '$');
+
+    let mut ai = FillerAi::new(1);
+    ai.update_board(map.width, map.height, board).unwrap();
+    assert!(ai.heat_map[0][3] > ai.heat_map[0][1]);
+}
+
+#[test]
+fn test_standard_maps_load() {
+    for name in STANDARD_MAPS {
+        let map = Map::standard(name).unwrap();
+        assert_eq!(Map::load(&format!("maps/{}", name)).unwrap(), map);
+    }
+    assert_eq!(Map::standard("map00").unwrap().width, 20);
+    assert!(Map::standard("map99").is_none());
+}
+
+#[test]
+fn test_generated_maps() {
+    let config = MapGeneratorConfig::default();
+
+    for seed in 0..50 {
+        let map = generate_map(&config, seed);
+        assert_eq!(map, generate_map(&config, seed));
+        assert!((15..=100).contains(&map.width));
+        assert!((15..=100).contains(&map.height));
+        assert_ne!(map.starts[0], map.starts[1]);
+        assert!(
+            Map::parse(&format!(
+                "size {} {}\n@ {} {}\n$ {} {}\n",
+                map.width,
+                map.height,
+                map.starts[0].0,
+                map.starts[0].1,
+                map.starts[1].0,
+                map.starts[1].1
+            ))
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{open_board, setup};
+use filler::board::Side;
+use filler::mcts::{MonteCarloSearch, Node};
+use filler::piece::Piece;
+
+fn capped(iterations: usize, seed: u64) -> MonteCarloSearch {
+    MonteCarloSearch {
+        time_budget: Duration::from_secs(10),
+        max_iterations: iterations,
+        seed,
+        ..MonteCarloSearch::default()
+    }
+}
+
+#[test]
+fn test_mcts_returns_legal_move() {
+    let ai = setup(
+        open_board(10, 8),
+        Piece::new(2, 2, vec![vec!['O', 'O'], vec!['O', '.']]),
+    );
+
+    let chosen = capped(100, 1).find_move(&ai).unwrap();
+    assert!(ai.find_all_valid_placements().contains(&chosen));
+}
+
+#[test]
+fn test_mcts_without_moves() {
+    let board = vec![
+        vec!['$', '$', '$'],
+        vec!['$', '@', '$'],
+        vec!['$', '$', '$'],
+    ];
+    let ai = setup(board, Piece::new(2, 1, vec![vec!['O', 'O']]));
+
+    assert!(capped(10, 0).find_move(&ai).is_none());
+    assert!(capped(10, 0).run(&ai).is_empty());
+}
+
+#[test]
+fn test_mcts_statistics() {
+    let ai = setup(
+        open_board(10, 8),
+        Piece::new(1, 2, vec![vec!['O'], vec!['O']]),
+    );
+    let stats = capped(200, 3).run(&ai);
+
+    // Every root move is expanded before any is selected again
+    assert!(stats.iter().all(|s| s.visits > 0));
+    assert_eq!(stats.iter().map(|s| s.visits).sum::<u32>(), 200);
+    assert!(stats.iter().all(|s| (0.0..=1.0).contains(&s.win_rate())));
+}
+
+#[test]
+fn test_mcts_is_reproducible_with_seed() {
+    let ai = setup(open_board(10, 8), Piece::new(2, 1, vec![vec!['O', 'O']]));
+
+    assert_eq!(capped(150, 9).run(&ai), capped(150, 9).run(&ai));
+}
+
+// Distance of a node from the root
+fn depth(nodes: &[Node], mut index: usize) -> usize {
+    let mut depth = 0;
+    while let Some(parent) = nodes[index].parent {
+        depth += 1;
+        index = parent;
+    }
+    depth
+}
+
+#[test]
+fn test_mcts_expands_replies_for_both_sides() {
+    let ai = setup(
+        open_board(10, 8),
+        Piece::new(2, 2, vec![vec!['O', 'O'], vec!['O', '.']]),
+    );
+    let nodes = capped(600, 5).search(&ai);
+
+    // Our moves lead to opponent nodes, their replies back to ours
+    for (index, node) in nodes.iter().enumerate() {
+        let expected = if depth(&nodes, index).is_multiple_of(2) {
+            Side::Me
+        } else {
+            Side::Opponent
+        };
+        assert_eq!(node.mover, expected);
+    }
+    assert!((0..nodes.len()).any(|index| depth(&nodes, index) >= 3));
+}
+
+#[test]
+fn test_mcts_backpropagates_every_playout() {
+    let ai = setup(open_board(10, 8), Piece::new(2, 1, vec![vec!['O', 'O']]));
+    let nodes = capped(400, 2).search(&ai);
+
+    // The root counts every iteration; any other node its own expansion and
+    // everything below it
+    assert_eq!(nodes[0].visits, 400);
+    for (index, node) in nodes.iter().enumerate() {
+        let below: u32 = node.children.iter().map(|&child| nodes[child].visits).sum();
+        let own = if index == 0 { 0 } else { 1 };
+        assert_eq!(node.visits, below + own);
+        assert!((0.0..=node.visits as f64).contains(&node.wins));
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::{board, grid};
+use filler::filler_ai::FillerAi;
+use filler::opponent::OpponentModel;
+use filler::piece::Piece;
+
+#[test]
+fn test_diff_records_new_cells() {
+    let mut model = OpponentModel::new();
+    model.observe(&board(&["@.....", "......", "....$."]), &[]);
+    assert!(model.moves.is_empty());
+
+    model.observe(&board(&["@.....", "....s.", "....$s"]), &[(4, 1), (5, 2)]);
+    let last = model.last_move().unwrap();
+    assert_eq!(last.cells, vec![(4, 1), (5, 2)]);
+    assert_eq!(last.turn, 1);
+    assert_eq!(last.origin, (4.0, 2.0));
+}
+
+#[test]
+fn test_first_board_uses_last_placed_cells() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(5, 3, grid(&["@....", "...ss", "...$."]))
+        .unwrap();
+
+    assert_eq!(ai.opponent.moves.len(), 1);
+    assert_eq!(ai.opponent.moves[0].cells, vec![(3, 1), (4, 1)]);
+    assert_eq!(ai.opponent.moves[0].origin, (3.0, 2.0));
+}
+
+#[test]
+fn test_growth_direction_follows_moves() {
+    let mut model = OpponentModel::new();
+    model.observe(&board(&["@.......", "$.......", "........"]), &[]);
+    model.observe(&board(&["@.......", "$s......", "........"]), &[(1, 1)]);
+    model.observe(&board(&["@.......", "$$s.....", "........"]), &[(2, 1)]);
+
+    let (dx, dy) = model.growth_direction().unwrap();
+    assert!(dx > 0.9 && dy.abs() < 0.1);
+    // Cells ahead of the growth are likelier than cells behind it
+    assert!(model.expansion_likelihood(4, 1, 8, 3) > model.expansion_likelihood(0, 2, 8, 3));
+}
+
+#[test]
+fn test_aggression_and_regions() {
+    let mut model = OpponentModel::new();
+    model.observe(&board(&["@@.......", ".........", "........$"]), &[]);
+    model.observe(&board(&["@@s......", ".........", "........$"]), &[(2, 0)]);
+    assert_eq!(model.aggression(), 1.0);
+
+    model.observe(&board(&["@@$......", ".........", ".......s$"]), &[(7, 2)]);
+    assert_eq!(model.aggression(), 0.5);
+    assert_eq!(model.preferred_region(), Some((0, 0)));
+    assert_eq!(model.region_preference(8, 2, 9, 3), 0.5);
+}
+
+#[test]
+fn test_no_history_means_no_prediction() {
+    let model = OpponentModel::new();
+
+    assert_eq!(model.growth_direction(), None);
+    assert_eq!(model.aggression(), 0.0);
+    assert_eq!(model.preferred_region(), None);
+    assert_eq!(model.expansion_likelihood(1, 1, 4, 4), 0.0);
+}
+
+#[test]
+fn test_ai_tracks_opponent_between_turns() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(6, 3, grid(&["@.....", "......", "....$."]))
+        .unwrap();
+    ai.update_board(6, 3, grid(&["@.....", "...s..", "...s$."]))
+        .unwrap();
+    ai.update_piece(Piece::new(1, 1, vec![vec!['O']]));
+
+    assert_eq!(ai.opponent.moves.len(), 1);
+    assert_eq!(ai.opponent.moves[0].cells, vec![(3, 1), (3, 2)]);
+    // A cell next to the opponent's last piece is a likelier target than the far corner
+    assert!(ai.calculate_anticipation_score(2, 1) > ai.calculate_anticipation_score(0, 2));
+}
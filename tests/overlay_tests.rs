@@ -0,0 +1,122 @@
+mod common;
+
+use std::time::Duration;
+
+use common::grid;
+use filler::filler_ai::FillerAi;
+use filler::overlay::{Overlay, ReplayAnalysis};
+use filler::piece::Piece;
+use filler::replay::{Replay, ReplayTurn};
+
+fn corner() -> Piece {
+    Piece::new(2, 2, vec![vec!['O', 'O'], vec!['.', 'O']])
+}
+
+// Three successive boards of one game, as the AI (player 1) received them
+fn boards() -> Vec<Vec<Vec<char>>> {
+    vec![
+        grid(&["........", ".@......", "........", "......$.", "........"]),
+        grid(&["........", ".@@.....", "..@.....", ".....s$.", "........"]),
+        grid(&["........", ".@@@....", ".@@.....", "....ss$.", "........"]),
+    ]
+}
+
+fn ai_at(turn: usize) -> FillerAi {
+    let mut ai = FillerAi::new(1);
+    for board in &boards()[..=turn] {
+        ai.update_board(8, 5, board.clone()).unwrap();
+    }
+    ai.update_piece(corner());
+    ai
+}
+
+#[test]
+fn test_heat_layer() {
+    let ai = ai_at(0);
+    let overlay = Overlay::from_ai(&ai, None);
+
+    assert_eq!(overlay.heat(1, 1), None);
+    assert_eq!(overlay.heat(6, 3), None);
+    // Next to the opponent is the hottest empty cell
+    assert_eq!(overlay.heat(5, 3), Some(1.0));
+    let far = overlay.heat(0, 0).unwrap();
+    assert!(far > 0.0 && far < 1.0);
+}
+
+#[test]
+fn test_score_layer() {
+    let ai = ai_at(1);
+    let moves = ai.find_all_valid_placements();
+    let overlay = Overlay::from_ai(&ai, None);
+
+    assert_eq!(overlay.candidates, moves.len());
+    let scored: Vec<f64> = (0..5)
+        .flat_map(|y| (0..8).map(move |x| (x, y)))
+        .filter_map(|(x, y)| overlay.score(x, y))
+        .collect();
+    assert!(scored.iter().all(|score| (0.0..=1.0).contains(score)));
+    assert!(scored.contains(&1.0) && scored.contains(&0.0));
+    // Far corner cells are out of reach of every candidate
+    assert_eq!(overlay.score(7, 0), None);
+    assert!(overlay.chosen.is_empty());
+}
+
+#[test]
+fn test_chosen_move_cells() {
+    let ai = ai_at(1);
+    let best = ai.find_best_move();
+    let overlay = Overlay::from_ai(&ai, best);
+
+    let (x, y) = best.unwrap();
+    let expected: Vec<(usize, usize)> = ai
+        .placed_cells(x, y)
+        .map(|(cx, cy)| (cx as usize, cy as usize))
+        .collect();
+    assert_eq!(overlay.chosen, expected);
+    assert_eq!(overlay.chosen.len(), 3);
+    // The best candidate's cells sit at the top of the score ramp
+    assert!(
+        overlay
+            .chosen
+            .iter()
+            .filter(|&&(cx, cy)| ai.cells.cell(cx, cy).is_empty())
+            .all(|&(cx, cy)| overlay.score(cx, cy) == Some(1.0))
+    );
+}
+
+#[test]
+fn test_contested_chokepoints() {
+    let mut ai = FillerAi::new(1);
+    // The corner cell is ours alone; the corridor is shared and pinches at every
+    // cell but its ends
+    let board = grid(&[".@@@@@@", "@@.....", "@@@@@@$"]);
+    ai.update_board(7, 3, board).unwrap();
+    ai.update_piece(corner());
+    let overlay = Overlay::from_ai(&ai, None);
+
+    assert_eq!(overlay.chokepoints, vec![(3, 1), (4, 1), (5, 1)]);
+}
+
+#[test]
+fn test_replay_analysis_matches_live_ai() {
+    let replay = Replay {
+        player_number: 1,
+        turns: boards()
+            .into_iter()
+            .enumerate()
+            .map(|(turn, board)| ReplayTurn {
+                board,
+                piece: corner(),
+                answer: Some((turn as i32, 0)),
+                elapsed: Duration::from_micros(50),
+            })
+            .collect(),
+    };
+    let mut analysis = ReplayAnalysis::new(&replay);
+
+    // Forward, back to the start, then forward again past a skipped turn
+    for turn in [0, 1, 2, 0, 2, 2] {
+        let expected = Overlay::from_ai(&ai_at(turn), Some((turn as i32, 0)));
+        assert_eq!(analysis.overlay(turn), expected, "turn {turn}");
+    }
+}
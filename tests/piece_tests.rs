@@ -1,5 +1,5 @@
 // tests/piece_tests.rs
-use filler::piece::Piece;
+use filler::piece::{Piece, PieceGenerator, PieceGeneratorConfig};
 
 #[test]
 fn test_piece_creation() {
@@ -111,3 +111,88 @@ fn test_complex_piece_pattern() {
     assert_eq!(piece.pattern[1][1], 'O'); // Center
     assert_eq!(piece.pattern[0][0], '.'); // Top left corner
 }
+
+#[test]
+fn test_solid_cells() {
+    let pattern = vec![vec!['.', 'O'], vec!['O', '.']];
+    let piece = Piece::new(2, 2, pattern);
+
+    assert_eq!(piece.solid_cells(), vec![(1, 0), (0, 1)]);
+}
+
+#[test]
+fn test_reach_ignores_empty_border() {
+    let pattern = vec![vec!['.', '.', '.', '.'], vec!['.', 'O', '.', 'O']];
+    assert_eq!(Piece::new(4, 2, pattern).reach(), 2);
+    assert_eq!(Piece::new(1, 1, vec![vec!['O']]).reach(), 0);
+    assert_eq!(Piece::new(0, 0, Vec::new()).reach(), 0);
+}
+
+fn is_connected(piece: &Piece) -> bool {
+    let cells = piece.solid_cells();
+    let mut seen = vec![cells[0]];
+    let mut stack = vec![cells[0]];
+    while let Some((x, y)) = stack.pop() {
+        for &(nx, ny) in &cells {
+            let adjacent = x.abs_diff(nx) + y.abs_diff(ny) == 1;
+            if adjacent && !seen.contains(&(nx, ny)) {
+                seen.push((nx, ny));
+                stack.push((nx, ny));
+            }
+        }
+    }
+    seen.len() == cells.len()
+}
+
+#[test]
+fn test_generator_is_reproducible() {
+    let mut first = PieceGenerator::new(PieceGeneratorConfig::default(), 7);
+    let mut second = PieceGenerator::new(PieceGeneratorConfig::default(), 7);
+    let mut other = PieceGenerator::new(PieceGeneratorConfig::default(), 8);
+
+    let first_pieces: Vec<Piece> = (0..20).map(|_| first.next_piece()).collect();
+    let second_pieces: Vec<Piece> = (0..20).map(|_| second.next_piece()).collect();
+    let other_pieces: Vec<Piece> = (0..20).map(|_| other.next_piece()).collect();
+
+    assert_eq!(first_pieces, second_pieces);
+    assert_ne!(first_pieces, other_pieces);
+}
+
+#[test]
+fn test_generator_respects_config() {
+    let config = PieceGeneratorConfig {
+        min_width: 2,
+        max_width: 4,
+        min_height: 3,
+        max_height: 5,
+        density: 0.5,
+        connected: true,
+    };
+    let mut generator = PieceGenerator::new(config, 1);
+
+    for _ in 0..200 {
+        let piece = generator.next_piece();
+        assert!((2..=4).contains(&piece.width));
+        assert!((3..=5).contains(&piece.height));
+        assert_eq!(piece.pattern.len(), piece.height);
+        assert!(piece.pattern.iter().all(|row| row.len() == piece.width));
+
+        let expected = ((piece.width * piece.height) as f64 * 0.5).round() as usize;
+        assert_eq!(piece.solid_cells().len(), expected);
+        assert!(is_connected(&piece));
+    }
+}
+
+#[test]
+fn test_generator_minimum_density() {
+    let config = PieceGeneratorConfig {
+        density: 0.0,
+        connected: false,
+        ..PieceGeneratorConfig::default()
+    };
+    let mut generator = PieceGenerator::new(config, 3);
+
+    for _ in 0..50 {
+        assert_eq!(generator.next_piece().solid_cells().len(), 1);
+    }
+}
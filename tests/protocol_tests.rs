@@ -0,0 +1,120 @@
+use std::io::Cursor;
+
+use filler::piece::Piece;
+use filler::protocol::{
+    ProtocolReader, format_turn, parse_board_header, parse_move, parse_piece_header,
+};
+
+const TURN: &str = "\
+Anfield 5 3:
+    01234
+000 .@...
+001 .....
+002 ...$.
+Piece 2 2:
+.O
+OO
+";
+
+fn reader(input: &str) -> ProtocolReader<Cursor<&str>> {
+    ProtocolReader::new(Cursor::new(input))
+}
+
+#[test]
+fn test_header_parsing() {
+    assert_eq!(parse_board_header("Anfield 20 15:"), Ok((20, 15)));
+    assert_eq!(parse_piece_header("Piece 4 1:"), Ok((4, 1)));
+    assert!(parse_board_header("Anfield 20:").is_err());
+    assert!(parse_piece_header("Piece four 1:").is_err());
+}
+
+#[test]
+fn test_player_line() {
+    assert_eq!(
+        reader("$$$ exec p1 : [filler]\n").read_player_number(),
+        Ok(1)
+    );
+    assert_eq!(
+        reader("$$$ exec p2 : [filler]\n").read_player_number(),
+        Ok(2)
+    );
+
+    let error = reader("hello\n").read_player_number().unwrap_err();
+    assert_eq!(error.line, 1);
+    assert_eq!(error.actual, "hello");
+}
+
+#[test]
+fn test_read_turn() {
+    let mut reader = reader(TURN);
+    let turn = reader.read_turn().unwrap().unwrap();
+
+    assert_eq!((turn.width, turn.height), (5, 3));
+    assert_eq!(turn.board[0], vec!['.', '@', '.', '.', '.']);
+    assert_eq!(turn.piece.pattern, vec![vec!['.', 'O'], vec!['O', 'O']]);
+    assert_eq!(reader.read_turn(), Ok(None));
+}
+
+#[test]
+fn test_short_row_is_reported() {
+    let input = TURN.replace("001 .....", "001 ....");
+    let error = reader(&input).read_turn().unwrap_err();
+
+    assert_eq!(error.line, 4);
+    assert!(error.expected.contains("width 5"));
+    assert_eq!(error.actual, "001 ....");
+}
+
+#[test]
+fn test_missing_rows_are_reported() {
+    let input: String = TURN
+        .lines()
+        .take(3)
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let error = reader(&input).read_turn().unwrap_err();
+
+    assert_eq!(error.line, 4);
+    assert_eq!(error.actual, "end of input");
+}
+
+#[test]
+fn test_bad_headers_and_cells_are_reported() {
+    let error = reader("Anfeld 5 3:\n").read_turn().unwrap_err();
+    assert_eq!(error.line, 1);
+    assert!(error.expected.contains("Anfield"));
+
+    let error = reader(&TURN.replace("002 ...$.", "002 ...X."))
+        .read_turn()
+        .unwrap_err();
+    assert_eq!(error.line, 5);
+
+    let error = reader(&TURN.replace("Piece 2 2:", "Piece 2 2"))
+        .read_turn()
+        .unwrap_err();
+    assert_eq!(error.line, 6);
+
+    let error = reader(&TURN.replace(".O\n", ".O.\n"))
+        .read_turn()
+        .unwrap_err();
+    assert_eq!(error.line, 7);
+    assert!(error.to_string().starts_with("line 7: expected piece row"));
+}
+
+#[test]
+fn test_format_turn_round_trip() {
+    let board = vec![vec!['.', '@', '.'], vec!['.', '.', 's']];
+    let piece = Piece::new(2, 1, vec![vec!['O', 'O']]);
+    let text = format_turn(&board, &piece);
+
+    let turn = reader(&text).read_turn().unwrap().unwrap();
+    assert_eq!(turn.board, board);
+    assert_eq!(turn.piece, piece);
+}
+
+#[test]
+fn test_parse_move() {
+    assert_eq!(parse_move("3 -1"), Some((3, -1)));
+    assert_eq!(parse_move("3"), None);
+    assert_eq!(parse_move("a b"), None);
+}
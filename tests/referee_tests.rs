@@ -0,0 +1,60 @@
+use filler::board::Cell;
+use filler::map::Map;
+use filler::piece::Piece;
+use filler::referee::Referee;
+
+fn small_map() -> Map {
+    Map::parse(".....\n.@...\n.....\n...$.\n").unwrap()
+}
+
+#[test]
+fn test_referee_starts_from_map() {
+    let referee = Referee::new(&small_map(), 0);
+
+    assert_eq!(referee.board_width, 5);
+    assert_eq!(referee.board_height, 4);
+    assert_eq!(referee.board.cell(1, 1), Cell::Mine);
+    assert_eq!(referee.board.cell(3, 3), Cell::Theirs);
+    assert_eq!(referee.grid()[1][1], '@');
+    assert_eq!(referee.scores(), (1, 1));
+}
+
+#[test]
+fn test_turn_format_matches_engine() {
+    let referee = Referee::new(&small_map(), 0);
+    let piece = Piece::new(2, 2, vec![vec!['O', 'O'], vec!['O', 'O']]);
+    let turn = referee.format_turn(&piece);
+
+    let mut lines = turn.lines();
+    assert_eq!(lines.next(), Some("Anfield 5 4:"));
+    assert_eq!(lines.next(), Some("    01234"));
+    assert_eq!(lines.next(), Some("000 ....."));
+    assert_eq!(lines.next(), Some("001 .@..."));
+    assert_eq!(lines.nth(2), Some("Piece 2 2:"));
+    assert_eq!(lines.next(), Some("OO"));
+}
+
+#[test]
+fn test_invalid_move_eliminates_player() {
+    let mut referee = Referee::new(&small_map(), 0);
+    let piece = Piece::new(2, 2, vec![vec!['O', 'O'], vec!['O', 'O']]);
+
+    assert!(referee.play_move(0, &piece, Some((0, 0))));
+    assert!(referee.active[0]);
+    assert_eq!(referee.scores(), (4, 1));
+
+    assert!(!referee.play_move(1, &piece, Some((0, 0))));
+    assert!(!referee.active[1]);
+    assert!(!referee.play_move(0, &piece, None));
+    assert!(referee.is_over());
+}
+
+#[test]
+fn test_seeded_pieces_are_reproducible() {
+    let mut first = Referee::new(&small_map(), 42);
+    let mut second = Referee::new(&small_map(), 42);
+
+    for _ in 0..10 {
+        assert_eq!(first.next_piece(), second.next_piece());
+    }
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::{board, random_board};
+use filler::board::Cell;
+use filler::endgame::Separation;
+use filler::regions::{Reach, Region, RegionAnalysis};
+use filler::rng::Rng;
+
+fn region(analysis: &RegionAnalysis, x: usize, y: usize) -> Option<&Region> {
+    analysis.label(x, y).map(|label| &analysis.regions[label])
+}
+
+fn reach(analysis: &RegionAnalysis, x: usize, y: usize) -> Option<Reach> {
+    region(analysis, x, y).map(|region| region.reach)
+}
+
+#[test]
+fn test_regions_and_reach() {
+    let analysis = RegionAnalysis::compute(&board(&["..@$..", "..@$..", "@@@$$$", "......"]));
+
+    assert_eq!(analysis.regions.len(), 3);
+    assert_eq!(reach(&analysis, 0, 0), Some(Reach::Mine));
+    assert_eq!(reach(&analysis, 5, 1), Some(Reach::Theirs));
+    assert_eq!(reach(&analysis, 0, 3), Some(Reach::Both));
+    assert_eq!(region(&analysis, 0, 0).map(|region| region.size), Some(4));
+    assert_eq!(region(&analysis, 3, 3).map(|region| region.size), Some(6));
+    assert_eq!(analysis.label(2, 0), None);
+    assert_eq!(analysis.label(6, 0), None);
+
+    let empty = RegionAnalysis::compute(&board(&["...", "..."]));
+    assert_eq!(reach(&empty, 1, 1), Some(Reach::Neither));
+    assert!(Reach::Both.includes_me() && Reach::Both.includes_them());
+    assert!(!Reach::Theirs.includes_me() && !Reach::Mine.includes_them());
+}
+
+#[test]
+fn test_diagonal_steps_join_regions() {
+    let analysis = RegionAnalysis::compute(&board(&[".@", "@."]));
+
+    assert_eq!(analysis.regions.len(), 1);
+    assert_eq!(analysis.label(0, 0), analysis.label(1, 1));
+}
+
+#[test]
+fn test_regions_split_when_filled() {
+    let before = board(&["@....", ".....", "....$"]);
+    let after = board(&["@.a..", "..a..", "..a.$"]);
+    let mut analysis = RegionAnalysis::compute(&before);
+    assert_eq!(analysis.region_count(), 1);
+
+    analysis.fill(&after, &[(2, 0), (2, 1), (2, 2)]);
+    assert_eq!(analysis.region_count(), 2);
+    assert_eq!(region(&analysis, 1, 0).map(|region| region.size), Some(5));
+    assert_eq!(reach(&analysis, 1, 0), Some(Reach::Mine));
+    assert_eq!(reach(&analysis, 3, 2), Some(Reach::Both));
+    assert_eq!(analysis.label(2, 1), None);
+    assert_eq!(analysis.open_neighbours(1, 1), 4);
+}
+
+#[test]
+fn test_corridor_chokepoints() {
+    let cells = board(&["@@@@@@@", "@.....@", "@@@@@@@"]);
+    let analysis = RegionAnalysis::compute(&cells);
+
+    assert_eq!(
+        analysis.chokepoints(&cells, 0),
+        vec![(2, 1), (3, 1), (4, 1)]
+    );
+    assert!(analysis.chokepoints(&cells, 1).is_empty());
+
+    // Two cells wide, every cell can be walked around
+    let wide = board(&["@.....", "@....."]);
+    assert!(
+        RegionAnalysis::compute(&wide)
+            .chokepoints(&wide, 0)
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_chokepoints_match_brute_force() {
+    let mut rng = Rng::new(13);
+    for _ in 0..20 {
+        let (width, height) = (rng.range(3, 12), rng.range(3, 10));
+        let cells = random_board(&mut rng, width, height, 0.5);
+        let analysis = RegionAnalysis::compute(&cells);
+        let chokepoints: Vec<(usize, usize)> = (0..analysis.regions.len())
+            .flat_map(|label| analysis.chokepoints(&cells, label))
+            .collect();
+
+        for (x, y, cell) in cells.iter() {
+            if !cell.is_empty() {
+                continue;
+            }
+            // Fill the cell and count the pieces its region falls into
+            let mut filled = cells.clone();
+            filled.set(x, y, Cell::Mine);
+            let after = RegionAnalysis::compute(&filled);
+            let mut pieces: Vec<usize> = cells
+                .iter()
+                .filter(|&(cx, cy, _)| {
+                    (cx, cy) != (x, y) && analysis.label(cx, cy) == analysis.label(x, y)
+                })
+                .filter_map(|(cx, cy, _)| after.label(cx, cy))
+                .collect();
+            pieces.sort_unstable();
+            pieces.dedup();
+
+            assert_eq!(
+                chokepoints.contains(&(x, y)),
+                pieces.len() > 1,
+                "({x}, {y})"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_open_neighbours() {
+    let analysis = RegionAnalysis::compute(&board(&["@..", ".$.", "..."]));
+
+    assert_eq!(analysis.open_neighbours(0, 0), 2);
+    assert_eq!(analysis.open_neighbours(1, 1), 7);
+    assert_eq!(analysis.open_neighbours(2, 2), 2);
+}
+
+#[test]
+fn test_separation_from_regions() {
+    let cells = board(&["..@$..", "..@$..", "@@@$$$", "......"]);
+    let analysis = RegionAnalysis::compute(&cells);
+
+    assert_eq!(analysis.cells_with_reach(Reach::Both), 6);
+    assert_eq!(
+        Separation::from_regions(&cells, &analysis, 1),
+        Separation {
+            mine: 4,
+            theirs: 4,
+            shared: 6,
+            unreachable: 0,
+        }
+    );
+    assert_eq!(
+        Separation::compute(&cells, 1),
+        Separation::from_regions(&cells, &analysis, 1)
+    );
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use common::args;
+use filler::board::Cell;
+use filler::options::Options;
+use filler::piece::Piece;
+use filler::replay::{Replay, ReplayCursor, ReplayTurn, ReplayWriter};
+
+fn turn(answer: Option<(i32, i32)>, micros: u64) -> ReplayTurn {
+    ReplayTurn {
+        board: vec![
+            vec!['.', '@', '.', '.'],
+            vec!['.', '.', '.', '.'],
+            vec!['.', '.', 's', '$'],
+        ],
+        piece: Piece::new(2, 2, vec![vec!['.', 'O'], vec!['O', 'O']]),
+        answer,
+        elapsed: Duration::from_micros(micros),
+    }
+}
+
+fn write(player_number: u8, turns: &[ReplayTurn]) -> String {
+    let mut out = Vec::new();
+    let mut writer = ReplayWriter::new(&mut out, player_number).unwrap();
+    for turn in turns {
+        writer
+            .record(&turn.board, &turn.piece, turn.answer, turn.elapsed)
+            .unwrap();
+    }
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+    let turns = vec![turn(Some((0, -1)), 1500), turn(None, 42)];
+    let text = write(2, &turns);
+
+    let replay = Replay::read(Cursor::new(text)).unwrap();
+    assert_eq!(replay.player_number, 2);
+    assert_eq!(replay.turns, turns);
+}
+
+#[test]
+fn test_file_format() {
+    let text = write(1, &[turn(Some((1, 0)), 2500)]);
+
+    assert_eq!(
+        text,
+        "$$$ exec p1 : [replay]\n\
+         Anfield 4 3:\n    0123\n000 .@..\n001 ....\n002 ..s$\n\
+         Piece 2 2:\n.O\nOO\n\
+         move 1 0 2500us\n"
+    );
+}
+
+#[test]
+fn test_empty_replay() {
+    let replay = Replay::read(Cursor::new(write(1, &[]))).unwrap();
+
+    assert_eq!(replay.player_number, 1);
+    assert!(replay.turns.is_empty());
+}
+
+#[test]
+fn test_bad_move_lines() {
+    let text = write(1, &[turn(Some((1, 0)), 10)]);
+
+    let error = Replay::read(Cursor::new(text.replace("10us", "10ms"))).unwrap_err();
+    assert_eq!((error.line, error.actual.as_str()), (10, "move 1 0 10ms"));
+
+    let truncated = text.replace("move 1 0 10us\n", "");
+    let error = Replay::read(Cursor::new(truncated)).unwrap_err();
+    assert_eq!((error.line, error.actual.as_str()), (10, "end of input"));
+}
+
+#[test]
+fn test_create_in_directory() {
+    let dir = std::env::temp_dir().join(format!("filler_replay_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut writer = ReplayWriter::create(dir.to_str().unwrap(), 2).unwrap();
+    let recorded = turn(Some((2, 1)), 7);
+    writer
+        .record(
+            &recorded.board,
+            &recorded.piece,
+            recorded.answer,
+            recorded.elapsed,
+        )
+        .unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].to_str().unwrap().ends_with("-p2.replay"));
+    let replay = Replay::load(files[0].to_str().unwrap()).unwrap();
+    assert_eq!(replay.turns, vec![recorded]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(Replay::load("/missing.replay").is_err());
+}
+
+#[test]
+fn test_replay_option() {
+    assert_eq!(Options::parse(args(&[]), |_| None).unwrap().replay, None);
+
+    let env = |key: &str| (key == "FILLER_REPLAY").then(|| "/tmp/env.replay".to_string());
+    assert_eq!(
+        Options::parse(args(&[]), env).unwrap().replay.as_deref(),
+        Some("/tmp/env.replay")
+    );
+    assert_eq!(
+        Options::parse(args(&["--replay", "/tmp/flag.replay"]), env)
+            .unwrap()
+            .replay
+            .as_deref(),
+        Some("/tmp/flag.replay")
+    );
+    assert!(Options::parse(args(&["--replay"]), |_| None).is_err());
+}
+
+#[test]
+fn test_cursor_stays_in_range() {
+    let mut cursor = ReplayCursor::new(25);
+
+    assert!(!cursor.step(-1));
+    assert!(cursor.step(10));
+    assert_eq!(cursor.turn, 10);
+    assert!(cursor.step(100));
+    assert_eq!(cursor.turn, 24);
+    assert!(cursor.at_end());
+    assert!(!cursor.last());
+    assert!(cursor.step(-3));
+    assert_eq!(cursor.turn, 21);
+    assert!(cursor.first() && cursor.turn == 0);
+
+    let mut empty = ReplayCursor::new(0);
+    assert!(!empty.step(1) && empty.at_end());
+}
+
+#[test]
+fn test_turn_cells_and_placement() {
+    let replay = Replay {
+        player_number: 2,
+        turns: vec![turn(Some((2, -1)), 1), turn(None, 1)],
+    };
+
+    // The piece's top row hangs off the board
+    assert_eq!(replay.turns[0].placed_cells(), vec![(2, 0), (3, 0)]);
+    assert!(replay.turns[1].placed_cells().is_empty());
+
+    let cells = replay.cells(0);
+    assert_eq!(cells.cell(3, 2), Cell::Mine);
+    assert_eq!(cells.cell(2, 2), Cell::MineLast);
+    assert_eq!(cells.cell(1, 0), Cell::Theirs);
+}
+
+#[test]
+fn test_final_cells() {
+    assert_eq!(Replay::default().final_cells(), None);
+
+    let replay = Replay {
+        player_number: 2,
+        turns: vec![turn(None, 1), turn(Some((1, 0)), 1)],
+    };
+    let board = replay.final_cells().unwrap();
+    // The answer covers (2, 0), (1, 1) and (2, 1); the piece's empty corner is untouched
+    for (x, y) in [(2, 0), (1, 1), (2, 1)] {
+        assert_eq!(board.cell(x, y), Cell::MineLast);
+    }
+    assert_eq!(board.cell(1, 0), Cell::Theirs);
+    assert_eq!(board.cell(3, 2), Cell::Mine);
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{board, random_board};
+use filler::bitboard::{Bitboard, CompiledPiece};
+use filler::board::{Board, Cell, Side};
+use filler::game_state::GameState;
+use filler::piece::{Piece, PieceGenerator, PieceGeneratorConfig};
+use filler::player::Player;
+use filler::rng::Rng;
+use filler::rules::{
+    anchored_placements, anchored_placements_with, apply_placement, count_territory,
+    has_no_isolated_cells, is_valid_placement, valid_placements,
+};
+
+#[test]
+fn test_single_overlap_is_valid() {
+    let board = board(&["@...", "....", "...$"]);
+    let piece = Piece::new(2, 1, vec![vec!['O', 'O']]);
+
+    assert!(is_valid_placement(&board, &piece, 0, 0, Side::Me));
+    assert!(!is_valid_placement(&board, &piece, 1, 0, Side::Me));
+}
+
+#[test]
+fn test_opponent_overlap_is_invalid() {
+    let board = board(&["@$..", "....", "...."]);
+    let piece = Piece::new(2, 1, vec![vec!['O', 'O']]);
+
+    assert!(!is_valid_placement(&board, &piece, 0, 0, Side::Me));
+}
+
+#[test]
+fn test_out_of_bounds_is_invalid() {
+    let board = board(&["...@", "....", "$..."]);
+    let piece = Piece::new(2, 1, vec![vec!['O', 'O']]);
+
+    assert!(!is_valid_placement(&board, &piece, 3, 0, Side::Me));
+    assert!(is_valid_placement(&board, &piece, 2, 0, Side::Me));
+}
+
+#[test]
+fn test_valid_placements_from_player_two() {
+    let board = board(&["@...", "....", "...s"]);
+    let piece = Piece::new(1, 1, vec![vec!['O']]);
+
+    let moves = valid_placements(&board, &piece, Side::Opponent);
+    assert_eq!(moves, vec![(3, 2)]);
+}
+
+#[test]
+fn test_apply_placement_marks_last_placed() {
+    let mut board = board(&["a...", "....", "...$"]);
+    let piece = Piece::new(2, 2, vec![vec!['O', 'O'], vec!['.', 'O']]);
+
+    apply_placement(&mut board, &piece, 0, 0, Side::Me);
+
+    let rows = |board: &Board| board.to_chars(&Player::new(1), &Player::new(2));
+    assert_eq!(rows(&board)[0], vec!['a', 'a', '.', '.']);
+    assert_eq!(rows(&board)[1], vec!['.', 'a', '.', '.']);
+    assert_eq!(count_territory(&board, Side::Me), 3);
+    assert_eq!(count_territory(&board, Side::Opponent), 1);
+
+    // Next placement turns the previous piece into plain territory
+    let single = Piece::new(1, 1, vec![vec!['O']]);
+    apply_placement(&mut board, &single, 1, 1, Side::Me);
+    assert_eq!(rows(&board)[0], vec!['@', '@', '.', '.']);
+    assert_eq!(board.cell(1, 1), Cell::MineLast);
+}
+
+#[test]
+fn test_isolated_cells() {
+    let diagonal = Piece::new(2, 2, vec![vec!['O', '.'], vec!['.', 'O']]);
+    let split = Piece::new(3, 1, vec![vec!['O', '.', 'O']]);
+    let single = Piece::new(2, 1, vec![vec!['.', 'O']]);
+
+    assert!(has_no_isolated_cells(&diagonal));
+    assert!(!has_no_isolated_cells(&split));
+    assert!(!has_no_isolated_cells(&single));
+}
+
+#[test]
+fn test_anchored_placements_match_full_scan() {
+    let mut rng = Rng::new(17);
+    let mut pieces = PieceGenerator::new(
+        PieceGeneratorConfig {
+            connected: false,
+            ..PieceGeneratorConfig::default()
+        },
+        4,
+    );
+
+    for _ in 0..100 {
+        let (width, height) = (rng.range(1, 25), rng.range(1, 15));
+        let board = random_board(&mut rng, width, height, 0.4);
+        let piece = pieces.next_piece();
+        let expected = valid_placements(&board, &piece, Side::Me);
+
+        // Every owned cell as an anchor works for any piece
+        let owned = board
+            .iter()
+            .filter(|(_, _, c)| c.is_mine())
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<_>>();
+        assert_eq!(anchored_placements(&board, &piece, owned.clone()), expected);
+        // Checked with the bitboard masks instead of the cells
+        assert_eq!(
+            anchored_placements_with(
+                &board,
+                Bitboard::from_board(&board).as_ref(),
+                &piece,
+                CompiledPiece::new(&piece).as_ref(),
+                owned,
+            ),
+            expected
+        );
+
+        // Owned cells next to empty space are enough when no piece cell stands alone
+        if has_no_isolated_cells(&piece) {
+            let frontier = GameState::new(&board);
+            assert_eq!(
+                anchored_placements(&board, &piece, frontier.my_frontier()),
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{grid, open_board, setup};
+use filler::board::Board;
+use filler::piece::Piece;
+use filler::player::Player;
+use filler::search::{AlphaBetaSearch, evaluate};
+
+#[test]
+fn test_search_returns_legal_move() {
+    let ai = setup(
+        open_board(8, 6),
+        Piece::new(2, 2, vec![vec!['O', 'O'], vec!['.', 'O']]),
+    );
+    let search = AlphaBetaSearch::default();
+
+    let chosen = search.find_move(&ai).unwrap();
+    assert!(ai.find_all_valid_placements().contains(&chosen));
+}
+
+#[test]
+fn test_search_without_moves() {
+    let board = vec![
+        vec!['$', '$', '$'],
+        vec!['$', '@', '$'],
+        vec!['$', '$', '$'],
+    ];
+    let ai = setup(board, Piece::new(2, 1, vec![vec!['O', 'O']]));
+
+    assert!(AlphaBetaSearch::default().find_move(&ai).is_none());
+}
+
+#[test]
+fn test_expired_budget_falls_back_to_heuristic_order() {
+    let ai = setup(
+        open_board(8, 6),
+        Piece::new(1, 2, vec![vec!['O'], vec!['O']]),
+    );
+    let search = AlphaBetaSearch {
+        time_budget: Duration::ZERO,
+        ..AlphaBetaSearch::default()
+    };
+
+    let chosen = search.find_move(&ai).unwrap();
+    let best_score = ai
+        .find_all_valid_placements()
+        .iter()
+        .map(|&(x, y)| ai.score_placement(x, y))
+        .max()
+        .unwrap();
+    assert_eq!(ai.score_placement(chosen.0, chosen.1), best_score);
+}
+
+#[test]
+fn test_search_is_deterministic_with_depth_limit() {
+    let ai = setup(open_board(8, 6), Piece::new(2, 1, vec![vec!['O', 'O']]));
+    let search = AlphaBetaSearch {
+        max_depth: 2,
+        time_budget: Duration::from_secs(10),
+        ..AlphaBetaSearch::default()
+    };
+
+    assert_eq!(search.find_move(&ai), search.find_move(&ai));
+}
+
+// Going down leaves (0, 0) as our only way out, and only a straight three along the
+// top row can fill it: the square never fits there, so every piece must be tried
+#[test]
+fn test_opponent_replies_try_every_likely_piece() {
+    let ai = setup(
+        grid(&["..$$", "@$.$", ".$.$"]),
+        Piece::new(1, 2, vec![vec!['O'], vec!['O']]),
+    );
+    let search = AlphaBetaSearch {
+        max_depth: 2,
+        time_budget: Duration::from_secs(10),
+        likely_pieces: vec![
+            Piece::new(2, 2, vec![vec!['O', 'O'], vec!['O', 'O']]),
+            Piece::new(3, 1, vec![vec!['O', 'O', 'O']]),
+        ],
+        ..AlphaBetaSearch::default()
+    };
+
+    assert_eq!(search.find_move(&ai), Some((0, 0)));
+}
+
+#[test]
+fn test_evaluate_prefers_more_territory() {
+    let (one, two) = (Player::new(1), Player::new(2));
+    let even = vec![vec!['@', '.', '.', '$']];
+    let ahead = vec![vec!['@', 'a', '.', '$']];
+
+    assert_eq!(evaluate(&Board::from_chars(&even, &one, &two).unwrap()), 0);
+    assert!(evaluate(&Board::from_chars(&ahead, &one, &two).unwrap()) > 0);
+    // The same board seen from player 2
+    assert!(evaluate(&Board::from_chars(&ahead, &two, &one).unwrap()) < 0);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::args;
+use filler::filler_ai::FillerAi;
+use filler::options::Options;
+use filler::piece::Piece;
+use filler::strategy::{HeuristicStrategy, STRATEGY_NAMES, Strategy, strategy_from_name};
+
+#[test]
+fn test_every_name_builds_a_strategy() {
+    for name in STRATEGY_NAMES {
+        let strategy = strategy_from_name(name).unwrap();
+        assert_eq!(strategy.name(), *name);
+    }
+    assert!(strategy_from_name("random").is_none());
+}
+
+#[test]
+fn test_heuristic_strategy_matches_find_best_move() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(
+        4,
+        2,
+        vec![vec!['@', '.', '.', 's'], vec!['.', '.', '.', '.']],
+    )
+    .unwrap();
+    ai.update_piece(Piece::new(1, 1, vec![vec!['O']]));
+
+    assert_eq!(HeuristicStrategy.choose_move(&ai), ai.find_best_move());
+}
+
+#[test]
+fn test_options_default_to_heuristic() {
+    let options = Options::parse(args(&[]), |_| None).unwrap();
+    assert_eq!(options.strategy, "heuristic");
+}
+
+#[test]
+fn test_options_flag_overrides_environment() {
+    let env = |key: &str| (key == "FILLER_STRATEGY").then(|| "mcts".to_string());
+
+    assert_eq!(Options::parse(args(&[]), env).unwrap().strategy, "mcts");
+    assert_eq!(
+        Options::parse(args(&["--strategy", "alphabeta"]), env)
+            .unwrap()
+            .strategy,
+        "alphabeta"
+    );
+}
+
+#[test]
+fn test_options_reject_unknown_values() {
+    assert!(Options::parse(args(&["--strategy", "random"]), |_| None).is_err());
+    assert!(Options::parse(args(&["--strategy"]), |_| None).is_err());
+    assert!(Options::parse(args(&["--fast"]), |_| None).is_err());
+}
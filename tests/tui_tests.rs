@@ -0,0 +1,143 @@
+mod common;
+
+use std::fs;
+use std::time::Duration;
+
+use common::args;
+use filler::board::{Board, Cell};
+use filler::options::Options;
+use filler::piece::Piece;
+use filler::tui::{TuiRenderer, render_frame};
+
+fn board() -> Board {
+    let mut board = Board::new(4, 3);
+    board.set(0, 0, Cell::Mine);
+    board.set(1, 0, Cell::MineLast);
+    board.set(3, 2, Cell::TheirsLast);
+    board
+}
+
+fn piece() -> Piece {
+    Piece::new(2, 2, vec![vec!['*', '*'], vec!['.', '*']])
+}
+
+// The frame with every colour code removed
+fn plain(frame: &str) -> String {
+    let mut text = String::new();
+    let mut chars = frame.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c.is_ascii_alphabetic());
+        } else {
+            text.push(c);
+        }
+    }
+    text
+}
+
+#[test]
+fn test_frame_layout() {
+    let frame = render_frame(
+        &board(),
+        1,
+        &piece(),
+        Some((1, 0)),
+        Duration::from_micros(250),
+        7,
+    );
+    let text = plain(&frame);
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines[0], "Filler p1 - turn 7 - move 1 0 in 0.250 ms");
+    // Two columns per cell; the answer covers (1, 0), (2, 0) and (2, 1)
+    assert_eq!(lines[1], "  [][]  ");
+    assert_eq!(lines[2], "    []  ");
+    assert_eq!(lines[3], "        ");
+    assert_eq!(lines[4], "   p1 (me) 2      p2 1      empty 9");
+    assert_eq!(lines[5], "piece 2x2");
+    assert_eq!(lines.len(), 8);
+}
+
+#[test]
+fn test_frame_without_answer() {
+    let frame = render_frame(&board(), 2, &piece(), None, Duration::ZERO, 1);
+    let text = plain(&frame);
+
+    assert!(text.starts_with("Filler p2 - turn 1 - no move in 0.000 ms\n"));
+    assert!(!text.contains("[]"));
+    assert!(text.contains("p2 (me) 2      p1 1"));
+}
+
+#[test]
+fn test_frame_colours() {
+    // Our cells stay red as player 1 and turn blue as player 2, like the visualizer
+    let red = "\x1b[48;2;255;100;100m";
+    let blue = "\x1b[48;2;100;100;255m";
+    let as_player1 = render_frame(&board(), 1, &piece(), None, Duration::ZERO, 1);
+    let as_player2 = render_frame(&board(), 2, &piece(), None, Duration::ZERO, 1);
+
+    assert!(as_player1.lines().nth(1).unwrap().starts_with(red));
+    assert!(as_player2.lines().nth(1).unwrap().starts_with(blue));
+    // Marked cells are drawn in gold over the cell colour
+    let marked = render_frame(&board(), 1, &piece(), Some((1, 0)), Duration::ZERO, 1);
+    assert!(marked.contains("\x1b[38;2;255;215;0m[]"));
+    // Each line resets its colours once, so nothing bleeds past the board
+    let first_row = marked.lines().nth(1).unwrap();
+    assert!(first_row.ends_with("\x1b[0m"));
+    assert_eq!(first_row.matches("\x1b[0m").count(), 1);
+    // A colour is only sent when it changes along the row: three empty cells, then
+    // theirs, then the reset
+    let last_row = marked.lines().nth(3).unwrap();
+    assert_eq!(last_row.matches('\x1b').count(), 3);
+}
+
+#[test]
+fn test_renderer_redraws_in_place() {
+    let mut out = Vec::new();
+    let mut renderer = TuiRenderer::new(&mut out);
+    renderer
+        .draw(&board(), 1, &piece(), Some((1, 0)), Duration::ZERO)
+        .unwrap();
+    renderer
+        .draw(&board(), 1, &piece(), None, Duration::ZERO)
+        .unwrap();
+    let frames: Vec<String> = String::from_utf8(out)
+        .unwrap()
+        .split("\x1b[H\x1b[2J")
+        .map(plain)
+        .collect();
+    // Each frame clears the screen first and the turn counter follows the draws
+    assert_eq!(frames.len(), 3);
+    assert!(frames[0].is_empty());
+    assert!(frames[1].starts_with("Filler p1 - turn 1 - move 1 0"));
+    assert!(frames[2].starts_with("Filler p1 - turn 2 - no move"));
+
+    let path = std::env::temp_dir().join(format!("filler-tui-{}.txt", std::process::id()));
+    let mut file = TuiRenderer::open(path.to_str().unwrap()).unwrap();
+    file.draw(&board(), 1, &piece(), None, Duration::ZERO)
+        .unwrap();
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(written.starts_with("\x1b[H\x1b[2JFiller p1 - turn 1 - "));
+    assert!(TuiRenderer::open("/nonexistent/dir/tui.txt").is_err());
+}
+
+#[test]
+fn test_tui_option() {
+    assert_eq!(Options::parse(args(&[]), |_| None).unwrap().tui, None);
+
+    let env = |key: &str| (key == "FILLER_TUI").then(|| "stderr".to_string());
+    assert_eq!(
+        Options::parse(args(&[]), env).unwrap().tui.as_deref(),
+        Some("stderr")
+    );
+    assert_eq!(
+        Options::parse(args(&["--tui", "/tmp/filler.tui"]), env)
+            .unwrap()
+            .tui
+            .as_deref(),
+        Some("/tmp/filler.tui")
+    );
+    assert!(Options::parse(args(&["--tui"]), |_| None).is_err());
+}
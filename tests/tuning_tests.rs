@@ -0,0 +1,129 @@
+use filler::config::ScoringConfig;
+use filler::filler_ai::FillerAi;
+use filler::map::{Map, MapGeneratorConfig};
+use filler::referee::Referee;
+use filler::rng::Rng;
+use filler::strategy::HeuristicStrategy;
+use filler::tuning::{TuningConfig, evaluate_scoring, evolve, mutate};
+
+fn small_maps() -> MapGeneratorConfig {
+    MapGeneratorConfig {
+        min_width: 10,
+        max_width: 14,
+        min_height: 8,
+        max_height: 12,
+        min_separation: 0.4,
+    }
+}
+
+#[test]
+fn test_in_process_game_finishes() {
+    let map = Map::standard("map00").unwrap();
+    let mut referee = Referee::new(&map, 4);
+    let mut ais = [FillerAi::new(1), FillerAi::new(2)];
+
+    let (first, second) =
+        referee.play_in_process(&mut ais, [&mut HeuristicStrategy, &mut HeuristicStrategy]);
+
+    assert!(referee.is_over());
+    assert!(first > 1 && second > 1);
+}
+
+#[test]
+fn test_mirror_match_is_even() {
+    let baseline = ScoringConfig::default();
+    let share = evaluate_scoring(&baseline, &baseline, 2, &small_maps(), 11);
+
+    // Same weights on both seats of the same map and pieces
+    assert!((share - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_mutation_keeps_weights_non_negative() {
+    let mut rng = Rng::new(5);
+    let zero = ScoringConfig {
+        heat_weight: 0,
+        blocking_weight: 0,
+        expansion_weight: 0,
+        efficiency_weight: 0,
+        territory_weight: 0,
+        anticipation_weight: 0,
+        proximity_bonus: 0,
+        efficiency_multiplier: 0,
+    };
+
+    for _ in 0..50 {
+        let mutated = mutate(&zero, 0.5, &mut rng);
+        assert!(
+            ScoringConfig::KEYS
+                .iter()
+                .all(|key| mutated.get(key).unwrap() >= 0)
+        );
+    }
+}
+
+#[test]
+fn test_evolve_reports_every_generation() {
+    let config = TuningConfig {
+        population: 3,
+        generations: 2,
+        games_per_candidate: 2,
+        elites: 1,
+        validation_games: 2,
+        // Every candidate plays whole games, so keep the boards tiny
+        maps: MapGeneratorConfig {
+            min_width: 6,
+            max_width: 8,
+            min_height: 5,
+            max_height: 6,
+            ..small_maps()
+        },
+        seed: 3,
+        ..TuningConfig::default()
+    };
+    let mut reported = Vec::new();
+
+    let best = evolve(
+        &config,
+        &ScoringConfig::default(),
+        |generation, candidate| {
+            reported.push((generation, candidate.clone()));
+        },
+    );
+
+    assert_eq!(reported.len(), 2);
+    assert!((0.0..=1.0).contains(&best.fitness));
+    assert!(
+        reported
+            .iter()
+            .all(|(_, c)| (0.0..=1.0).contains(&c.fitness))
+    );
+
+    // The winner is picked on the held-out games, where no generation's best beats it
+    let held_out = |scoring: &ScoringConfig| {
+        evaluate_scoring(
+            scoring,
+            &ScoringConfig::default(),
+            config.validation_games,
+            &config.maps,
+            config.validation_seed(),
+        )
+    };
+    assert_eq!(best.fitness, held_out(&best.scoring));
+    assert!(
+        reported
+            .iter()
+            .all(|(_, candidate)| held_out(&candidate.scoring) <= best.fitness)
+    );
+}
+
+#[test]
+fn test_report_round_trips_through_config_loader() {
+    let scoring = ScoringConfig {
+        heat_weight: 77,
+        efficiency_multiplier: 4,
+        ..ScoringConfig::default()
+    };
+
+    assert_eq!(ScoringConfig::parse(&scoring.to_toml()).unwrap(), scoring);
+}
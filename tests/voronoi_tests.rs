@@ -0,0 +1,98 @@
+mod common;
+
+use common::{board_as, grid};
+use filler::filler_ai::FillerAi;
+use filler::map::{MapGeneratorConfig, generate_map};
+use filler::piece::Piece;
+use filler::referee::Referee;
+use filler::voronoi::{TerritoryControl, territory_after_placement, territory_control};
+
+#[test]
+fn test_even_split() {
+    let control = territory_control(&board_as(&["@...$"], 1));
+
+    assert_eq!(
+        control,
+        TerritoryControl {
+            mine: 1,
+            theirs: 1,
+            contested: 1
+        }
+    );
+    assert_eq!(control.margin(), 0);
+}
+
+#[test]
+fn test_walls_shut_out_the_opponent() {
+    let control = territory_control(&board_as(&["..@..", "..@..", "$.@.."], 1));
+
+    // The far side of our wall is ours alone; near the opponent cells are split or theirs
+    assert_eq!(control.mine, 8);
+    assert_eq!(control.theirs, 1);
+    assert_eq!(control.contested, 2);
+}
+
+#[test]
+fn test_perspective_is_symmetric() {
+    let rows = ["@....", ".....", "....s"];
+    let ours = territory_control(&board_as(&rows, 1));
+    let theirs = territory_control(&board_as(&rows, 2));
+
+    assert_eq!(ours.mine, theirs.theirs);
+    assert_eq!(ours.theirs, theirs.mine);
+    assert_eq!(ours.contested, theirs.contested);
+}
+
+#[test]
+fn test_cutting_placement_gains_territory() {
+    let board = board_as(&["@@@..", ".....", ".....", ".....", "....$"], 1);
+    let vertical = Piece::new(1, 3, vec![vec!['O'], vec!['O'], vec!['O']]);
+
+    // Growing straight down the edge gains nothing, pushing into the middle does
+    let before = territory_control(&board);
+    let down = territory_after_placement(&board, &vertical, 0, 0);
+    let middle = territory_after_placement(&board, &vertical, 2, 0);
+    assert_eq!(down.margin(), before.margin());
+    assert!(middle.margin() > down.margin());
+}
+
+#[test]
+fn test_territory_score_in_ai() {
+    let mut ai = FillerAi::new(1);
+    ai.update_board(5, 3, grid(&["@....", ".....", "....$"]))
+        .unwrap();
+    ai.update_piece(Piece::new(2, 1, vec![vec!['O', 'O']]));
+
+    let expected = territory_after_placement(&ai.cells, &ai.current_piece, 0, 0).margin();
+    assert_eq!(ai.calculate_territory_score(0, 0), expected);
+    assert!(ai.calculate_territory_score(0, 0) > 0);
+}
+
+#[test]
+fn test_patched_territory_matches_fresh_bfs() {
+    let map = generate_map(&MapGeneratorConfig::default(), 9);
+    let mut referee = Referee::new(&map, 9);
+    let mut ai = FillerAi::new(1);
+
+    for _ in 0..12 {
+        let piece = referee.next_piece();
+        ai.update_board(referee.board_width, referee.board_height, referee.grid())
+            .unwrap();
+        ai.update_piece(piece.clone());
+
+        for (x, y) in ai.find_all_valid_placements() {
+            let fresh = territory_after_placement(&ai.cells, &ai.current_piece, x, y);
+            assert_eq!(ai.calculate_territory_score(x, y), fresh.margin());
+        }
+
+        let reply = ai.find_best_move();
+        referee.play_move(0, &piece, reply);
+        let piece = referee.next_piece();
+        let mut opponent = FillerAi::new(2);
+        opponent
+            .update_board(referee.board_width, referee.board_height, referee.grid())
+            .unwrap();
+        opponent.update_piece(piece.clone());
+        referee.play_move(1, &piece, opponent.find_best_move());
+    }
+}